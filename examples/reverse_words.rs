@@ -31,12 +31,11 @@ fn main() {
             .join(" ")
     );
 
-    // inefficient temporary strings
     println!(
         "{}",
         "foo bar baz"
             .split_whitespace()
-            .map(|s| s.chars().rev().map(|c| format!("<{c}>")).join(""))
+            .map(|s| s.chars().rev().join_with("", |c, f| write!(f, "<{c}>")))
             .join(" ")
     );
 
@@ -11,6 +11,7 @@ fn main() -> std::io::Result<()> {
     println!("{}", "äüö".chars().join(' '));
     std::env::args().join(", ").write_io(std::io::stdout())?;
     println!();
+    println!("{}", std::env::args().join(", ").once());
 
     // inefficient temporary string
     let str: String = std::env::args().join(", ").into();
@@ -8,7 +8,8 @@
 //! meaning on all iterators and collections. The elements and the separator
 //! need to implement [`std::fmt::Display`]. Alternatively the
 //! [`Join::join_str()`] method can be used to join elements that only
-//! implement [`AsRef<str>`].
+//! implement [`AsRef<str>`]. Separators that depend on their position or on
+//! the elements around them can be written by implementing [`Separator`].
 //!
 //! # Examples
 //!
@@ -49,6 +50,15 @@
 //! # }
 //! ```
 //!
+//! Or you can turn the [`Joiner`] into a [`OnceJoiner`] that consumes the
+//! iterator when it is displayed the first time.
+//!
+//! ```
+//! # use join_string::Join;
+//! #
+//! println!("{}", std::env::args().join(", ").once());
+//! ```
+//!
 //! # Notes
 //!
 //! The standard library already provides a similar [`std::slice::Join`]
@@ -58,249 +68,5166 @@
 //! more restrictions on element and separator types or always returning a
 //! [`String`].
 
+// =============================================================================
+//      macros joiner_options and debug_options
+// =============================================================================
+
+/// Defines the builder methods that are shared by [`Joiner`], [`TryJoiner`]
+/// and [`JoinerRef`]. All of them have an `options` field of type
+/// `JoinOptions`.
+macro_rules! joiner_options {
+    () => {
+        /// Write `prefix` before the joined elements.
+        ///
+        /// Per default the prefix is also written if the iterator is empty. Use
+        /// [`Joiner::wrap_empty()`] to change that.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!([1, 2, 3].join(", ").prefix("values: ").into_string(), "values: 1, 2, 3");
+        /// ```
+        #[inline]
+        pub fn prefix(mut self, prefix: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.prefix = prefix.into();
+            self
+        }
+
+        /// Write `suffix` after the joined elements.
+        ///
+        /// Per default the suffix is also written if the iterator is empty. Use
+        /// [`Joiner::wrap_empty()`] to change that.
+        #[inline]
+        pub fn suffix(mut self, suffix: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.suffix = suffix.into();
+            self
+        }
+
+        /// Surround the joined elements with `open` and `close`.
+        ///
+        /// This is the same as `.prefix(open).suffix(close)`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!(["foo", "bar"].join(", ").wrap("[", "]").into_string(), "[foo, bar]");
+        ///
+        /// let empty: [&str; 0] = [];
+        /// assert_eq!(empty.join(", ").wrap("[", "]").into_string(), "[]");
+        /// assert_eq!(empty.join(", ").wrap("[", "]").wrap_empty(false).into_string(), "");
+        /// ```
+        #[inline]
+        pub fn wrap(
+            self,
+            open: impl Into<std::borrow::Cow<'static, str>>,
+            close: impl Into<std::borrow::Cow<'static, str>>,
+        ) -> Self {
+            self.prefix(open).suffix(close)
+        }
+
+        /// Set whether the prefix and suffix are written when the iterator is
+        /// empty. Defaults to `true`.
+        #[inline]
+        pub fn wrap_empty(mut self, wrap_empty: bool) -> Self {
+            self.options.wrap_empty = wrap_empty;
+            self
+        }
+
+        /// Write `placeholder` instead of nothing if the iterator is empty.
+        ///
+        /// The placeholder is surrounded by the prefix and suffix if
+        /// [`Joiner::wrap_empty()`] is `true` (the default). It can be any
+        /// [`std::fmt::Display`] value and is formatted once, when it is set.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// let empty: [&str; 0] = [];
+        /// assert_eq!(empty.join(", ").placeholder("(none)").into_string(), "(none)");
+        /// assert_eq!(empty.join(", ").wrap("[", "]").placeholder('-').into_string(), "[-]");
+        /// assert_eq!(["foo"].join(", ").placeholder("(none)").into_string(), "foo");
+        ///
+        /// let none: Vec<u32> = Vec::new();
+        /// assert_eq!(none.join(" + ").placeholder(0).into_string(), "0");
+        /// ```
+        #[inline]
+        pub fn placeholder(mut self, placeholder: impl std::fmt::Display) -> Self {
+            self.options.placeholder = Some(placeholder.to_string());
+            self
+        }
+
+        /// Use `sep` instead of the regular separator between the last two
+        /// elements.
+        ///
+        /// This is useful for natural-language lists. If there are exactly two
+        /// elements this separator is used between them, unless a
+        /// [`Joiner::pair_sep()`] is set as well.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!(["a", "b", "c"].join(", ").last_sep(" and ").into_string(), "a, b and c");
+        /// assert_eq!(["a", "b"].join(", ").last_sep(" and ").into_string(), "a and b");
+        /// assert_eq!(["a"].join(", ").last_sep(" and ").into_string(), "a");
+        /// ```
+        #[inline]
+        pub fn last_sep(mut self, sep: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.last_sep = Some(sep.into());
+            self
+        }
+
+        /// Use `sep` as the separator if there are exactly two elements.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// let list = |items: &[&str]| items.iter().join(", ").last_sep(", or ").pair_sep(" or ").into_string();
+        ///
+        /// assert_eq!(list(&["a", "b", "c"]), "a, b, or c");
+        /// assert_eq!(list(&["a", "b"]), "a or b");
+        /// ```
+        #[inline]
+        pub fn pair_sep(mut self, sep: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.pair_sep = Some(sep.into());
+            self
+        }
+
+        /// Write at most `max` elements. If there are more elements the
+        /// [elision marker](Joiner::elision_marker()) is written after them.
+        ///
+        /// The number of remaining elements is taken from
+        /// [`Iterator::size_hint()`] if it is exact (as it is for all
+        /// [`ExactSizeIterator`]s), otherwise the rest of the iterator is drained
+        /// to count them. If only one element remains it is written instead of
+        /// the elision marker, since that wouldn't make the output any shorter.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!((1..=10).join(", ").limit(3).into_string(), "1, 2, 3, …");
+        /// assert_eq!(
+        ///     (1..=45).join(", ").limit(3).elision_marker("… (+{} more)").into_string(),
+        ///     "1, 2, 3, … (+42 more)"
+        /// );
+        /// assert_eq!((1..=4).join(", ").limit(3).into_string(), "1, 2, 3, 4");
+        /// ```
+        #[inline]
+        pub fn limit(mut self, max: usize) -> Self {
+            self.options.limit = Some(Limit { head: max, tail: 0 });
+            self
+        }
+
+        /// Set the marker that is written in place of the elements skipped
+        /// because of [`Joiner::limit()`] or [`Joiner::limit_ends()`]. Defaults
+        /// to `"…"`.
+        ///
+        /// The first `{}` in the marker is replaced by the number of skipped
+        /// elements.
+        #[inline]
+        pub fn elision_marker(mut self, marker: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.elision_marker = marker.into();
+            self
+        }
+
+        /// Write at most `max` bytes of output. If the output would be longer it
+        /// is cut at a UTF-8 character boundary and the
+        /// [truncation marker](Joiner::truncation_marker()) is appended.
+        ///
+        /// The limit includes the prefix and suffix, but not the truncation
+        /// marker. Writing stops at the element that exceeds the limit, so the
+        /// elements after it are not consumed. Use
+        /// [`Joiner::write_fmt_budgeted()`], [`Joiner::write_io_budgeted()`] or
+        /// [`Joiner::into_string_budgeted()`] to find out if the output was
+        /// truncated.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!(["foo", "bar", "baz"].join(", ").max_bytes(7).into_string(), "foo, ba…");
+        /// assert_eq!(["foo", "bar", "baz"].join(", ").max_bytes(13).into_string(), "foo, bar, baz");
+        /// assert_eq!(["äöü"].join(", ").max_bytes(3).into_string(), "ä…");
+        /// ```
+        #[inline]
+        pub fn max_bytes(mut self, max: usize) -> Self {
+            self.options.budget = Some(Budget::Bytes(max));
+            self
+        }
+
+        /// Write at most `max` characters (Unicode scalar values) of output. If
+        /// the output would be longer it is cut and the
+        /// [truncation marker](Joiner::truncation_marker()) is appended.
+        ///
+        /// See [`Joiner::max_bytes()`] for details.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!(["äöü", "ß"].join(", ").max_chars(5).into_string(), "äöü, …");
+        /// ```
+        #[inline]
+        pub fn max_chars(mut self, max: usize) -> Self {
+            self.options.budget = Some(Budget::Chars(max));
+            self
+        }
+
+        /// Set the marker that is appended when the output is truncated because of
+        /// [`Joiner::max_bytes()`] or [`Joiner::max_chars()`]. Defaults to `"…"`.
+        #[inline]
+        pub fn truncation_marker(mut self, marker: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.truncation_marker = marker.into();
+            self
+        }
+
+        /// Format every element using the flags of `spec`.
+        ///
+        /// Per default elements are formatted with the flags of the
+        /// [`std::fmt::Formatter`] the joiner is formatted with, except for width,
+        /// fill and alignment, which apply to the whole output. The separator,
+        /// prefix, suffix and markers are always written verbatim.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::{FormatSpec, Join};
+        ///
+        /// assert_eq!(
+        ///     [1.0, 2.5, -3.25].join(", ").element_format(FormatSpec::new().precision(1).sign_plus(true)).into_string(),
+        ///     "+1.0, +2.5, -3.2"
+        /// );
+        /// assert_eq!(
+        ///     ["a", "bb", "ccc"].join("|").element_format(FormatSpec::new().width(4).fill('.').align(std::fmt::Alignment::Center)).into_string(),
+        ///     ".a..|.bb.|ccc."
+        /// );
+        /// ```
+        #[inline]
+        pub fn element_format(mut self, spec: FormatSpec) -> Self {
+            self.options.element_format = Some(spec);
+            self
+        }
+
+        /// Set where separators are written. Per default they are only written
+        /// between the elements.
+        ///
+        /// With [`SepMode::Terminator`] the separator is written after every
+        /// element, e.g. for line-oriented output. With [`SepMode::Leading`] it is
+        /// written in front of every element. In both cases nothing is written for
+        /// an empty iterator, and [`Joiner::last_sep()`] and
+        /// [`Joiner::pair_sep()`] still replace the separator between the last two
+        /// elements.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::{Join, SepMode};
+        ///
+        /// assert_eq!(["foo", "bar"].join('\n').sep_mode(SepMode::Terminator).into_string(), "foo\nbar\n");
+        /// assert_eq!(["a", "b"].join('\0').sep_mode(SepMode::Terminator).into_string(), "a\0b\0");
+        /// assert_eq!(["usr", "bin"].join('/').sep_mode(SepMode::Leading).into_string(), "/usr/bin");
+        ///
+        /// let empty: [&str; 0] = [];
+        /// assert_eq!(empty.join('\n').sep_mode(SepMode::Terminator).into_string(), "");
+        /// ```
+        #[inline]
+        pub fn sep_mode(mut self, mode: SepMode) -> Self {
+            self.options.sep_mode = mode;
+            self
+        }
+
+        /// Write `indent` at the start of every line of every element, e.g. for
+        /// elements that span multiple lines. Empty lines are not indented.
+        ///
+        /// The elements are not buffered for this. Use [`Joiner::hanging_indent()`]
+        /// in order to not indent the first line of every element.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!(
+        ///     ["foo\nbar", "baz"].join('\n').indent("> ").into_string(),
+        ///     "> foo\n> bar\n> baz"
+        /// );
+        /// ```
+        #[inline]
+        pub fn indent(mut self, indent: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.indent = Some(indent.into());
+            self.options.hanging_indent = false;
+            self
+        }
+
+        /// Write `indent` at the start of every line of every element, except for
+        /// the first line. See [`Joiner::indent()`].
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// let errors = ["file not found:\nfoo.txt", "permission denied"];
+        /// assert_eq!(
+        ///     errors.join("\n- ").prefix("- ").hanging_indent("  ").into_string(),
+        ///     "- file not found:\n  foo.txt\n- permission denied"
+        /// );
+        /// ```
+        #[inline]
+        pub fn hanging_indent(mut self, indent: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+            self.options.indent = Some(indent.into());
+            self.options.hanging_indent = true;
+            self
+        }
+
+        /// Write a number in front of every element, e.g. for numbered lists.
+        ///
+        /// Elided elements keep their numbers. If the size of the iterator is
+        /// exactly known (see [`Joiner::limit()`]) the numbers are aligned. See
+        /// [`Numbering`] for the available styles.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::{Join, NumberStyle, Numbering};
+        ///
+        /// assert_eq!(
+        ///     ["foo", "bar"].join('\n').numbered(Numbering::default()).into_string(),
+        ///     "1. foo\n2. bar"
+        /// );
+        /// assert_eq!(
+        ///     ["foo", "bar"].join(' ').numbered(Numbering::new(NumberStyle::LowerAlpha).template("{})")).into_string(),
+        ///     "a)foo b)bar"
+        /// );
+        /// ```
+        #[inline]
+        pub fn numbered(mut self, numbering: Numbering) -> Self {
+            self.options.numbering = Some(numbering);
+            self
+        }
+
+        /// Write the group separator of `chunks` instead of the separator between
+        /// every group of elements, e.g. for digit grouping or hex dumps.
+        ///
+        /// The group separator is written verbatim. [`Joiner::last_sep()`] and
+        /// [`Joiner::pair_sep()`] take precedence over it. See [`Chunks`] for how
+        /// the groups are aligned.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::{ChunkAlign, Chunks, Join};
+        ///
+        /// assert_eq!(
+        ///     "DE89370400440532013000".chars().join("").chunked(Chunks::new(4, " ")).into_string(),
+        ///     "DE89 3704 0044 0532 0130 00"
+        /// );
+        /// assert_eq!(
+        ///     "1234567".bytes().map(char::from).join("").chunked(Chunks::new(3, ",").align(ChunkAlign::End)).into_string(),
+        ///     "1,234,567"
+        /// );
+        /// ```
+        #[inline]
+        pub fn chunked(mut self, chunks: Chunks) -> Self {
+            self.options.chunks = Some(chunks);
+            self
+        }
+    };
+}
+
+/// Defines the builder methods for [`std::fmt::Debug`] that are shared by
+/// [`Joiner`] and [`JoinerRef`]. [`TryJoiner`] doesn't implement
+/// [`std::fmt::Debug`], so it doesn't get them.
+macro_rules! debug_options {
+    () => {
+        /// Set whether [`std::fmt::Debug`] writes the elements as a bracketed
+        /// list, the same way as for slices. Defaults to `false`.
+        ///
+        /// In that case the separator, prefix, suffix, placeholder,
+        /// [`Joiner::last_sep()`], [`Joiner::pair_sep()`],
+        /// [`Joiner::sep_mode()`], [`Joiner::numbered()`] and
+        /// [`Joiner::chunked()`] are not used for [`std::fmt::Debug`], and all
+        /// flags apply to every element. This is useful for messages of
+        /// [`assert!()`].
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use join_string::Join;
+        ///
+        /// assert_eq!(format!("{:?}", ["a", "b"].join(", ").debug_list(true)), r#"["a", "b"]"#);
+        /// assert_eq!(format!("{:#?}", ["a", "b"].join(", ").debug_list(true)), format!("{:#?}", ["a", "b"]));
+        /// assert_eq!(format!("{}", ["a", "b"].join(", ").debug_list(true)), "a, b");
+        /// ```
+        #[inline]
+        pub fn debug_list(mut self, debug_list: bool) -> Self {
+            self.options.debug_list = debug_list;
+            self
+        }
+    };
+}
+
 // =============================================================================
 //      struct Joiner
 // =============================================================================
 
 /// Helper struct that captures the iterator and separator for later joining.
-pub struct Joiner<I, S>
+///
+/// When formatted using [`std::fmt::Display`] or [`std::fmt::Debug`] the
+/// width, fill and alignment flags apply to the whole output, like they do
+/// for strings. All other flags apply to every element, unless an
+/// [element format](Joiner::element_format()) is given.
+///
+/// ```
+/// use join_string::Join;
+///
+/// assert_eq!(format!("{:>12.1}", [1.0, 2.0].join(", ")), "    1.0, 2.0");
+/// ```
+///
+/// Using `{:#?}` writes every element on its own line with the trailing
+/// whitespace of the separator removed. If there is a prefix the elements are
+/// indented and the suffix is put on its own line, like
+/// [`std::fmt::Formatter::debug_list()`] does it. All flags apply to every
+/// element then. See also [`Joiner::debug_list()`].
+///
+/// ```
+/// use join_string::Join;
+///
+/// assert_eq!(
+///     format!("{:#?}", [Some(1), None].join(", ").wrap("{", "}")),
+///     "{\n    Some(\n        1,\n    ),\n    None\n}"
+/// );
+/// ```
+///
+/// [`std::fmt::LowerHex`], [`std::fmt::UpperHex`], [`std::fmt::Octal`],
+/// [`std::fmt::Binary`], [`std::fmt::LowerExp`] and [`std::fmt::UpperExp`]
+/// are implemented as well if the elements implement them. For those all
+/// flags apply to every element and the separator is written without flags.
+///
+/// ```
+/// use join_string::Join;
+///
+/// let mac = [0x00u8, 0x1b, 0x63, 0x84, 0x45, 0xe6];
+/// assert_eq!(format!("{:02x}", mac.join(':')), "00:1b:63:84:45:e6");
+/// assert_eq!(format!("{:#06b}", [1, 2].join(", ")), "0b0001, 0b0010");
+/// ```
+pub struct Joiner<I, S, F = DefaultFormat>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
 {
     iter: I,
     sep: S,
+    fmt: F,
+    options: JoinOptions,
+    /// Set by [`Joiner::limit_ends()`], since only there the iterator is
+    /// known to be a [`DoubleEndedIterator`].
+    take_back: Option<TakeBack<I>>,
 }
 
 impl<I, S> Joiner<I, S>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
+    S: Separator<I::Item>,
 {
     /// Create a [`Joiner`] object.
     ///
     /// You can use this when implementing your own `join()` function.
     #[inline]
     pub fn new(iter: I, sep: S) -> Self {
-        Self { iter, sep }
+        Self {
+            iter,
+            sep,
+            fmt: DefaultFormat,
+            options: JoinOptions::default(),
+            take_back: None,
+        }
+    }
+}
+
+impl<I, S, F> Joiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    joiner_options!();
+    debug_options!();
+
+    /// Write at most the first `head` and the last `tail` elements. If there
+    /// are at least two more elements the
+    /// [elision marker](Joiner::elision_marker()) is written in place of the
+    /// elements in between.
+    ///
+    /// The last `tail` elements are taken from the back of the iterator using
+    /// [`DoubleEndedIterator::next_back()`]. If the size of the iterator is
+    /// exactly known (see [`Joiner::limit()`]) the elements in between are
+    /// skipped, otherwise they are drained to count them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// assert_eq!(
+    ///     ('a'..='z').join(", ").limit_ends(2, 2).into_string(),
+    ///     "a, b, …, y, z"
+    /// );
+    /// assert_eq!(
+    ///     ('a'..='z').filter(|c| *c != 'x').join(", ").limit_ends(2, 2).elision_marker("({} more)").into_string(),
+    ///     "a, b, (21 more), y, z"
+    /// );
+    /// assert_eq!(('a'..='e').join(", ").limit_ends(2, 2).into_string(), "a, b, c, d, e");
+    /// ```
+    #[inline]
+    pub fn limit_ends(mut self, head: usize, tail: usize) -> Self
+    where
+        I: std::iter::DoubleEndedIterator,
+    {
+        self.options.limit = Some(Limit { head, tail });
+        self.take_back = Some(take_back::<I>);
+        self
+    }
+
+    /// Set what [`Joiner::into_string()`] does if formatting an element or a
+    /// separator fails. Defaults to [`ErrorPolicy::DebugAssert`].
+    #[inline]
+    pub fn on_error(mut self, policy: ErrorPolicy) -> Self {
+        self.options.on_error = policy;
+        self
+    }
+
+    /// Write every element using `fmt` instead of its own formatting trait
+    /// implementation.
+    ///
+    /// `fmt` is called with a reference to the element and the
+    /// [`std::fmt::Formatter`] of the output, so no temporary [`String`]
+    /// is created per element. It is used for [`std::fmt::Debug`] as well.
+    /// See also [`Join::join_with()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// assert_eq!(
+    ///     [1, 2, 3].join(", ").wrap("[", "]").format_with(|n, f| write!(f, "#{n}")).into_string(),
+    ///     "[#1, #2, #3]"
+    /// );
+    /// ```
+    #[inline]
+    pub fn format_with<G>(self, fmt: G) -> Joiner<I, S, G>
+    where
+        G: Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    {
+        self.with_format(fmt)
+    }
+
+    /// Like [`Joiner::format_with()`], but for any element formatter.
+    #[inline]
+    fn with_format<G>(self, fmt: G) -> Joiner<I, S, G> {
+        Joiner {
+            iter: self.iter,
+            sep: self.sep,
+            fmt,
+            options: self.options,
+            take_back: self.take_back,
+        }
     }
 
     /// Consumes the backing iterator of a [`Joiner`] and returns the joined elements as a new [`String`].
+    ///
+    /// If formatting an element or a separator fails the
+    /// [error policy](Joiner::on_error()) decides whether this panics or
+    /// returns the partial output. Use [`Joiner::try_into_string()`] to handle
+    /// such errors instead.
     #[inline]
     pub fn into_string(self) -> String
-    where I::Item: std::fmt::Display {
+    where F: DisplayElement<I::Item> {
+        let policy = self.options.on_error;
         let mut buffer = String::new();
-        let _ = self.write_fmt(&mut buffer);
+        if let Err(error) = self.write_checked(&mut buffer) {
+            policy.handle(error);
+        }
         buffer
     }
 
-    /// Consumes the backing iterator of a [`Joiner`] and writes the joined elements into a [`std::fmt::Write`].
-    pub fn write_fmt<W: std::fmt::Write>(mut self, mut writer: W) -> std::fmt::Result
-    where I::Item: std::fmt::Display {
-        if let Some(first) = self.iter.next() {
-            write!(writer, "{}", first)?;
-            for item in self.iter {
-                write!(writer, "{}{}", self.sep, item)?;
-            }
+    /// Consumes the backing iterator of a [`Joiner`] and returns the joined elements as a new [`String`],
+    /// or the index of the element whose formatting failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// struct Fails;
+    ///
+    /// impl std::fmt::Display for Fails {
+    ///     fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         Err(std::fmt::Error)
+    ///     }
+    /// }
+    ///
+    /// let items: [&dyn std::fmt::Display; 3] = [&1, &2, &Fails];
+    /// let error = items.join(", ").try_into_string().unwrap_err();
+    /// assert_eq!(error.index(), Some(2));
+    /// ```
+    #[inline]
+    pub fn try_into_string(self) -> Result<String, JoinError>
+    where F: DisplayElement<I::Item> {
+        let mut buffer = String::new();
+        self.write_checked(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Writes the joined elements into `writer`, reporting any error as a
+    /// [`JoinError`]. Formatting into a [`String`] can only fail because of
+    /// the elements or separators.
+    fn write_checked<W: std::fmt::Write>(self, mut writer: W) -> Result<JoinState, JoinError>
+    where F: DisplayElement<I::Item> {
+        let joiner = OnceJoiner::new(self);
+        let result = writer.write_fmt(format_args!("{}", joiner));
+        let state = joiner.state.get();
+        match result {
+            Ok(()) => Ok(state),
+            Err(_) => Err(state.error.unwrap_or(JoinError::format())),
         }
-        Ok(())
+    }
+
+    /// Turn the [`Joiner`] into a [`OnceJoiner`], which implements
+    /// [`std::fmt::Display`] and [`std::fmt::Debug`] even if the iterator
+    /// doesn't implement [`Clone`], by consuming the iterator when it is
+    /// formatted the first time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// println!("{}", std::env::args().join(" ").once());
+    /// ```
+    #[inline]
+    pub fn once(self) -> OnceJoiner<I, S, F> {
+        OnceJoiner::new(self)
+    }
+
+    /// Consumes the backing iterator of a [`Joiner`] and writes the joined elements into a [`std::fmt::Write`].
+    #[inline]
+    pub fn write_fmt<W: std::fmt::Write>(self, mut writer: W) -> std::fmt::Result
+    where F: DisplayElement<I::Item> {
+        write!(writer, "{}", OnceJoiner::new(self))
     }
 
     /// Consumes the backing iterator of a [`Joiner`] and writes the joined elements into a [`std::io::Write`].
-    pub fn write_io<W: std::io::Write>(mut self, mut writer: W) -> std::io::Result<()>
-    where I::Item: std::fmt::Display {
-        if let Some(first) = self.iter.next() {
-            write!(writer, "{}", first)?;
-            for item in self.iter {
-                write!(writer, "{}{}", self.sep, item)?;
+    #[inline]
+    pub fn write_io<W: std::io::Write>(self, mut writer: W) -> std::io::Result<()>
+    where F: DisplayElement<I::Item> {
+        write!(writer, "{}", OnceJoiner::new(self))
+    }
+
+    /// Like [`Joiner::into_string()`], but also returns whether the output was
+    /// truncated because of [`Joiner::max_bytes()`] or [`Joiner::max_chars()`].
+    #[inline]
+    pub fn into_string_budgeted(self) -> (String, bool)
+    where F: DisplayElement<I::Item> {
+        let policy = self.options.on_error;
+        let mut buffer = String::new();
+        match self.write_checked(&mut buffer) {
+            Ok(state) => (buffer, state.truncated),
+            Err(error) => {
+                policy.handle(error);
+                (buffer, false)
             }
         }
-        Ok(())
+    }
+
+    /// Like [`Joiner::write_fmt()`], but returns whether the output was
+    /// truncated because of [`Joiner::max_bytes()`] or [`Joiner::max_chars()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// # fn main() -> std::fmt::Result {
+    /// let mut buffer = String::new();
+    /// let truncated = (1..100).join(",").max_bytes(10).write_fmt_budgeted(&mut buffer)?;
+    /// assert_eq!(buffer, "1,2,3,4,5,…");
+    /// assert!(truncated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_fmt_budgeted<W: std::fmt::Write>(self, mut writer: W) -> Result<bool, std::fmt::Error>
+    where F: DisplayElement<I::Item> {
+        let joiner = OnceJoiner::new(self);
+        write!(writer, "{}", joiner)?;
+        Ok(joiner.state.get().truncated)
+    }
+
+    /// Like [`Joiner::write_io()`], but returns whether the output was
+    /// truncated because of [`Joiner::max_bytes()`] or [`Joiner::max_chars()`].
+    pub fn write_io_budgeted<W: std::io::Write>(self, mut writer: W) -> std::io::Result<bool>
+    where F: DisplayElement<I::Item> {
+        let joiner = OnceJoiner::new(self);
+        write!(writer, "{}", joiner)?;
+        Ok(joiner.state.get().truncated)
+    }
+
+    /// Like [`Joiner::write_fmt()`], but returns how many elements,
+    /// separators and bytes were written.
+    ///
+    /// If writing fails the statistics up to that point are part of the
+    /// returned [`WriteStatsError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// # fn main() -> std::fmt::Result {
+    /// let mut buffer = String::new();
+    /// let stats = ["foo", "bär"].join(", ").write_fmt_stats(&mut buffer)?;
+    /// assert_eq!(stats.elements, 2);
+    /// assert_eq!(stats.separators, 1);
+    /// assert_eq!(stats.bytes, 9);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_fmt_stats<W: std::fmt::Write>(
+        self,
+        writer: W,
+    ) -> Result<WriteStats, WriteStatsError<std::fmt::Error>>
+    where F: DisplayElement<I::Item> {
+        let mut writer = CountingWriter::new(writer);
+        let joiner = OnceJoiner::new(self);
+        let result = std::fmt::Write::write_fmt(&mut writer, format_args!("{}", joiner));
+        let stats = joiner.state.get().stats(writer.bytes);
+        match result {
+            Ok(()) => Ok(stats),
+            Err(error) => Err(WriteStatsError { error, stats }),
+        }
+    }
+
+    /// Like [`Joiner::write_io()`], but returns how many elements,
+    /// separators and bytes were written.
+    ///
+    /// If writing fails the statistics up to that point are part of the
+    /// returned [`WriteStatsError`].
+    pub fn write_io_stats<W: std::io::Write>(
+        self,
+        writer: W,
+    ) -> Result<WriteStats, WriteStatsError<std::io::Error>>
+    where F: DisplayElement<I::Item> {
+        let mut writer = CountingWriter::new(writer);
+        let joiner = OnceJoiner::new(self);
+        let result = std::io::Write::write_fmt(&mut writer, format_args!("{}", joiner));
+        let stats = joiner.state.get().stats(writer.bytes);
+        match result {
+            Ok(()) => Ok(stats),
+            Err(error) => Err(WriteStatsError { error, stats }),
+        }
+    }
+}
+
+impl<I, S, F> From<Joiner<I, S, F>> for String
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+    F: DisplayElement<I::Item>,
+{
+    #[inline]
+    fn from(value: Joiner<I, S, F>) -> Self {
+        value.into_string()
+    }
+}
+
+impl<I, S, F> Clone for Joiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+    F: DisplayElement<I::Item>,
+    I: Clone,
+    S: Clone,
+    F: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            sep: self.sep.clone(),
+            fmt: self.fmt.clone(),
+            options: self.options.clone(),
+            take_back: self.take_back,
+        }
+    }
+}
+
+impl<I, S, F> std::fmt::Display for Joiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+    F: DisplayElement<I::Item>,
+    I: Clone,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_padded(
+            || self.iter.clone(),
+            self.take_back,
+            &self.sep,
+            &self.options,
+            f,
+            |item, f| self.fmt.fmt_display(item, f),
+        )
+    }
+}
+
+impl<I, S, F> std::fmt::Debug for Joiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+    F: DebugElement<I::Item>,
+    I: Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fmt_item =
+            |item: &I::Item, f: &mut std::fmt::Formatter<'_>| self.fmt.fmt_debug(item, f);
+        if self.options.debug_list || f.alternate() {
+            return fmt_debug_layout(
+                self.iter.clone(),
+                self.take_back,
+                &self.sep,
+                &self.options,
+                f,
+                fmt_item,
+                &mut JoinState::default(),
+            );
+        }
+        fmt_padded(
+            || self.iter.clone(),
+            self.take_back,
+            &self.sep,
+            &self.options,
+            f,
+            fmt_item,
+        )
+    }
+}
+
+// =============================================================================
+//      struct JoinOptions
+// =============================================================================
+
+/// The settings of a [`Joiner`] besides the iterator and the separator.
+#[derive(Debug, Clone)]
+struct JoinOptions {
+    prefix: std::borrow::Cow<'static, str>,
+    suffix: std::borrow::Cow<'static, str>,
+    wrap_empty: bool,
+    placeholder: Option<String>,
+    last_sep: Option<std::borrow::Cow<'static, str>>,
+    pair_sep: Option<std::borrow::Cow<'static, str>>,
+    limit: Option<Limit>,
+    elision_marker: std::borrow::Cow<'static, str>,
+    budget: Option<Budget>,
+    truncation_marker: std::borrow::Cow<'static, str>,
+    on_error: ErrorPolicy,
+    element_format: Option<FormatSpec>,
+    debug_list: bool,
+    sep_mode: SepMode,
+    numbering: Option<Numbering>,
+    chunks: Option<Chunks>,
+    indent: Option<std::borrow::Cow<'static, str>>,
+    hanging_indent: bool,
+}
+
+impl Default for JoinOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            prefix: std::borrow::Cow::Borrowed(""),
+            suffix: std::borrow::Cow::Borrowed(""),
+            wrap_empty: true,
+            placeholder: None,
+            last_sep: None,
+            pair_sep: None,
+            limit: None,
+            elision_marker: std::borrow::Cow::Borrowed("…"),
+            budget: None,
+            truncation_marker: std::borrow::Cow::Borrowed("…"),
+            on_error: ErrorPolicy::default(),
+            element_format: None,
+            debug_list: false,
+            sep_mode: SepMode::Between,
+            numbering: None,
+            chunks: None,
+            indent: None,
+            hanging_indent: false,
+        }
+    }
+}
+
+/// What happened while writing a [`Joiner`], for the methods that report it.
+#[derive(Debug, Clone, Copy, Default)]
+struct JoinState {
+    elements: usize,
+    separators: usize,
+    elided: usize,
+    truncated: bool,
+    error: Option<JoinError>,
+}
+
+impl JoinState {
+    /// Records `error` and returns a [`std::fmt::Error`] to be propagated.
+    #[inline]
+    fn fail(&mut self, error: JoinError) -> std::fmt::Error {
+        self.error = Some(error);
+        std::fmt::Error
+    }
+
+    #[inline]
+    fn stats(&self, bytes: usize) -> WriteStats {
+        WriteStats {
+            elements: self.elements,
+            separators: self.separators,
+            elided: self.elided,
+            bytes,
+            truncated: self.truncated,
+        }
+    }
+}
+
+/// Writes the elements of `iter` interspersed with `sep` according to
+/// `options`. This is what all the output methods of [`Joiner`] boil down to.
+///
+/// Elements are written using `fmt_item`, which allows the same code to be
+/// used for [`std::fmt::Display`] and [`std::fmt::Debug`].
+fn fmt_joined<I, S>(
+    iter: I,
+    take_back: Option<TakeBack<I>>,
+    sep: &S,
+    options: &JoinOptions,
+    f: &mut std::fmt::Formatter<'_>,
+    fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    state: &mut JoinState,
+) -> std::fmt::Result
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    let Some(budget) = options.budget else {
+        return fmt_entries(iter, take_back, sep, options, f, fmt_item, state);
+    };
+
+    // the elements are written through a new formatter, which needs the
+    // flags of `f`
+    let fmt_item = element_fmt_item(FormatSpec::from_formatter(f), options, fmt_item);
+    let mut writer = BudgetWriter::new(&mut *f, budget);
+    let result = std::fmt::Write::write_fmt(
+        &mut writer,
+        format_args!(
+            "{}",
+            FmtOnce::new(|f: &mut std::fmt::Formatter<'_>| {
+                fmt_entries(iter, take_back, sep, options, f, fmt_item, &mut *state)
+            })
+        ),
+    );
+
+    if writer.exhausted {
+        state.truncated = true;
+        state.error = None;
+        return f.write_str(&options.truncation_marker);
+    }
+
+    result
+}
+
+/// Writes the elements of `iter` for the formatting traits that treat the
+/// output as a whole, i.e. [`std::fmt::Display`] and [`std::fmt::Debug`].
+///
+/// The width, fill and alignment of `f` are applied to the whole output, like
+/// they are for strings. The other flags of `f` are applied to every element,
+/// unless an [element format](Joiner::element_format()) is set. Padding needs
+/// the length of the output, so in that case `iter` is called twice: once for
+/// measuring and once for writing the output.
+fn fmt_padded<I, S>(
+    iter: impl Fn() -> I,
+    take_back: Option<TakeBack<I>>,
+    sep: &S,
+    options: &JoinOptions,
+    f: &mut std::fmt::Formatter<'_>,
+    fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+) -> std::fmt::Result
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    let Some(width) = f.width() else {
+        return fmt_joined(iter(), take_back, sep, options, f, fmt_item, &mut JoinState::default());
+    };
+
+    let fmt_item = padded_fmt_item(f, options, fmt_item);
+    let render = FmtFn(|f: &mut std::fmt::Formatter<'_>| {
+        fmt_joined(iter(), take_back, sep, options, f, &fmt_item, &mut JoinState::default())
+    });
+
+    let mut counter = CharCounter::default();
+    std::fmt::Write::write_fmt(&mut counter, format_args!("{}", render))?;
+    write_padded(f, width, counter.chars, |f| f.write_fmt(format_args!("{}", render)))
+}
+
+/// Returns how to format the elements when the output is padded as a whole:
+/// with the flags of `f` except for width, fill and alignment.
+#[inline]
+fn padded_fmt_item<'a, T>(
+    f: &std::fmt::Formatter<'_>,
+    options: &'a JoinOptions,
+    fmt_item: impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result + 'a,
+) -> impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result + 'a {
+    let spec = FormatSpec {
+        width: None,
+        align: None,
+        ..FormatSpec::from_formatter(f)
+    };
+    element_fmt_item(spec, options, fmt_item)
+}
+
+/// Returns how to format the elements when they are not written to the
+/// [`std::fmt::Formatter`] the joiner is formatted with: with the flags of
+/// `spec`.
+fn element_fmt_item<'a, T>(
+    spec: FormatSpec,
+    options: &'a JoinOptions,
+    fmt_item: impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result + 'a,
+) -> impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result + 'a {
+    move |item, f| {
+        if options.element_format.is_some() {
+            // the element format is applied by the caller
+            fmt_item(item, f)
+        } else {
+            spec.fmt_with(item, f, &fmt_item)
+        }
+    }
+}
+
+/// Pads what `write` writes to `width` using the fill and alignment of `f`,
+/// given that `write` writes `chars` characters. The default alignment is
+/// left, like for strings.
+fn write_padded(
+    f: &mut std::fmt::Formatter<'_>,
+    width: usize,
+    chars: usize,
+    write: impl FnOnce(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+) -> std::fmt::Result {
+    let align = f.align().unwrap_or(std::fmt::Alignment::Left);
+    let (before, after) = padding(align, width.saturating_sub(chars));
+    let fill = f.fill();
+    write_fill(&mut *f, fill, before)?;
+    write(f)?;
+    write_fill(f, fill, after)
+}
+
+/// Writes the elements of `iter` for [`std::fmt::Debug`] if
+/// [`Joiner::debug_list()`] is set or `{:#?}` is used. All flags of `f` apply
+/// to every element then.
+fn fmt_debug_layout<I, S>(
+    iter: I,
+    take_back: Option<TakeBack<I>>,
+    sep: &S,
+    options: &JoinOptions,
+    f: &mut std::fmt::Formatter<'_>,
+    fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    state: &mut JoinState,
+) -> std::fmt::Result
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    if !options.debug_list {
+        return fmt_pretty(iter, sep, options, f, fmt_item, state);
+    }
+
+    let options = JoinOptions {
+        prefix: std::borrow::Cow::Borrowed("["),
+        suffix: std::borrow::Cow::Borrowed("]"),
+        wrap_empty: true,
+        placeholder: None,
+        last_sep: None,
+        pair_sep: None,
+        sep_mode: SepMode::Between,
+        numbering: None,
+        chunks: None,
+        ..options.clone()
+    };
+    if f.alternate() {
+        fmt_pretty(iter, &',', &options, f, fmt_item, state)
+    } else {
+        fmt_joined(iter, take_back, &", ", &options, f, fmt_item, state)
+    }
+}
+
+/// Writes the elements of `iter` on separate lines for `{:#?}`, with the
+/// trailing whitespace of `sep` removed. Leading separators are put at the
+/// start of the lines instead.
+///
+/// If there is a prefix the elements are indented by four spaces, and if
+/// there is a suffix it is put on its own line. For
+/// [`Joiner::debug_list()`] the separator is also written after the last
+/// element, like [`std::fmt::Formatter::debug_list()`] does it.
+fn fmt_pretty<I, S>(
+    iter: I,
+    sep: &S,
+    options: &JoinOptions,
+    f: &mut std::fmt::Formatter<'_>,
+    fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    state: &mut JoinState,
+) -> std::fmt::Result
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    let mut iter = iter.peekable();
+    if iter.peek().is_none() {
+        return fmt_joined(iter, None, sep, options, f, fmt_item, state);
+    }
+
+    let fmt_item = element_fmt_item(FormatSpec::from_formatter(f), options, fmt_item);
+    let line_sep = LineSep {
+        sep,
+        leading: options.sep_mode == SepMode::Leading,
+    };
+    let body = JoinOptions {
+        prefix: std::borrow::Cow::Borrowed(""),
+        suffix: std::borrow::Cow::Borrowed(""),
+        ..options.clone()
+    };
+
+    f.write_str(&options.prefix)?;
+    let indent = if options.prefix.is_empty() {
+        ""
+    } else {
+        f.write_str("\n")?;
+        "    "
+    };
+
+    let mut writer = PadAdapter::new(&mut *f, indent);
+    std::fmt::Write::write_fmt(
+        &mut writer,
+        format_args!(
+            "{}",
+            FmtOnce::new(|f: &mut std::fmt::Formatter<'_>| {
+                fmt_joined(iter, None, &line_sep, &body, f, fmt_item, state)
+            })
+        ),
+    )?;
+    if options.debug_list {
+        std::fmt::Write::write_str(&mut writer, ",")?;
+    }
+
+    // a terminator already ends the line
+    if !options.suffix.is_empty() && options.sep_mode != SepMode::Terminator {
+        f.write_str("\n")?;
+    }
+    f.write_str(&options.suffix)
+}
+
+/// Writes the elements of `iter` without applying a [`Budget`].
+fn fmt_entries<I, S>(
+    iter: I,
+    take_back: Option<TakeBack<I>>,
+    sep: &S,
+    options: &JoinOptions,
+    f: &mut std::fmt::Formatter<'_>,
+    fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    state: &mut JoinState,
+) -> std::fmt::Result
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    let len = match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None,
+    };
+    let number_width = match (&options.numbering, len) {
+        (Some(numbering), Some(len)) => numbering.width(len),
+        _ => 0,
+    };
+    let chunk_offset = match (&options.chunks, len) {
+        (Some(chunks), None) if chunks.align == ChunkAlign::End && chunks.size > 0 => {
+            // the groups can't be counted from the end without the size
+            let items: Vec<_> = iter.collect();
+            return fmt_entries(items.into_iter(), None, sep, options, f, fmt_item, state);
+        }
+        (Some(chunks), len) => chunks.offset(len),
+        (None, _) => 0,
+    };
+    let mut entries = Entries::new(iter, options.limit, take_back);
+    let Some(first) = entries.next() else {
+        if options.wrap_empty {
+            f.write_str(&options.prefix)?;
+        }
+        if let Some(placeholder) = &options.placeholder {
+            f.write_str(placeholder)?;
+        }
+        if options.wrap_empty {
+            f.write_str(&options.suffix)?;
+        }
+        return Ok(());
+    };
+
+    f.write_str(&options.prefix)?;
+    if options.sep_mode == SepMode::Leading {
+        write_sep(sep, options, None, Some(&first), chunk_offset, f, state)?;
+        state.separators += 1;
+    }
+    first.fmt(options, f, &fmt_item, number_width, state)?;
+    let mut prev = first;
+
+    if options.last_sep.is_none() && options.pair_sep.is_none() {
+        for entry in entries {
+            write_sep(sep, options, Some(&prev), Some(&entry), chunk_offset, f, state)?;
+            state.separators += 1;
+            entry.fmt(options, f, &fmt_item, number_width, state)?;
+            prev = entry;
+        }
+    } else {
+        // Look one element ahead in order to know which separator to use.
+        let mut index = 1;
+        let mut next = entries.next();
+        while let Some(entry) = next {
+            next = entries.next();
+            match (&options.pair_sep, &options.last_sep) {
+                (Some(pair_sep), _) if index == 1 && next.is_none() => f.write_str(pair_sep)?,
+                (_, Some(last_sep)) if next.is_none() => f.write_str(last_sep)?,
+                _ => write_sep(sep, options, Some(&prev), Some(&entry), chunk_offset, f, state)?,
+            }
+            state.separators += 1;
+            entry.fmt(options, f, &fmt_item, number_width, state)?;
+            prev = entry;
+            index += 1;
+        }
+    }
+
+    if options.sep_mode == SepMode::Terminator {
+        write_sep(sep, options, Some(&prev), None, chunk_offset, f, state)?;
+        state.separators += 1;
+    }
+    f.write_str(&options.suffix)
+}
+
+/// Writes `sep` between the entries `prev` and `next`, which are [`None`] for
+/// a leading separator or a terminator. The separator is written without the
+/// flags of `f`.
+fn write_sep<T, S>(
+    sep: &S,
+    options: &JoinOptions,
+    prev: Option<&Entry<T>>,
+    next: Option<&Entry<T>>,
+    chunk_offset: usize,
+    f: &mut std::fmt::Formatter<'_>,
+    state: &mut JoinState,
+) -> std::fmt::Result
+where
+    S: Separator<T>,
+{
+    // the index of the element after the separator
+    let before = match (prev, next) {
+        (_, Some(next)) => next.index(),
+        (Some(prev), None) => prev.end(),
+        (None, None) => 0,
+    };
+    if let (Some(chunks), Some(_), Some(_)) = (&options.chunks, prev, next) {
+        if chunks.is_boundary(before, chunk_offset) {
+            return f
+                .write_str(&chunks.sep)
+                .map_err(|_| state.fail(JoinError::separator(before)));
+        }
+    }
+    let index = match options.sep_mode {
+        SepMode::Leading => before,
+        SepMode::Between | SepMode::Terminator => before - 1,
+    };
+    f.write_fmt(format_args!(
+        "{}",
+        FmtFn(|f: &mut std::fmt::Formatter<'_>| {
+            sep.fmt_sep(
+                index,
+                prev.and_then(Entry::item),
+                next.and_then(Entry::item),
+                f,
+            )
+        })
+    ))
+    .map_err(|_| state.fail(JoinError::separator(before)))
+}
+
+/// How many elements to write before and after the elision marker.
+#[derive(Debug, Clone, Copy)]
+struct Limit {
+    head: usize,
+    tail: usize,
+}
+
+/// What is written in the place of an element: either the element at `index`
+/// or the elision marker that stands for `count` skipped elements starting at
+/// `index`.
+enum Entry<T> {
+    Item { index: usize, item: T },
+    Elided { index: usize, count: usize },
+}
+
+impl<T> Entry<T> {
+    #[inline]
+    fn index(&self) -> usize {
+        match self {
+            Entry::Item { index, .. } | Entry::Elided { index, .. } => *index,
+        }
+    }
+
+    /// The index after the element(s) of this entry.
+    #[inline]
+    fn end(&self) -> usize {
+        match self {
+            Entry::Item { index, .. } => index + 1,
+            Entry::Elided { index, count } => index + count,
+        }
+    }
+
+    #[inline]
+    fn item(&self) -> Option<&T> {
+        match self {
+            Entry::Item { item, .. } => Some(item),
+            Entry::Elided { .. } => None,
+        }
+    }
+
+    /// Writes the entry, with its number padded to `number_width` if
+    /// [`Joiner::numbered()`] is set.
+    fn fmt(
+        &self,
+        options: &JoinOptions,
+        f: &mut std::fmt::Formatter<'_>,
+        fmt_item: impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+        number_width: usize,
+        state: &mut JoinState,
+    ) -> std::fmt::Result {
+        match self {
+            Entry::Item { index, item } => {
+                if let Some(numbering) = &options.numbering {
+                    numbering.write_label(&mut *f, *index, number_width)?;
+                }
+                match (&options.indent, &options.element_format) {
+                    (Some(indent), spec) => {
+                        // the element is written to a new formatter, so the
+                        // flags of `f` need to be passed on explicitly
+                        let spec = spec.unwrap_or_else(|| FormatSpec::from_formatter(f));
+                        let writer = if options.hanging_indent {
+                            PadAdapter::hanging(&mut *f, indent)
+                        } else {
+                            PadAdapter::new(&mut *f, indent)
+                        };
+                        let mut writer = writer.skip_blank_lines();
+                        std::fmt::Write::write_fmt(
+                            &mut writer,
+                            format_args!(
+                                "{}",
+                                FmtFn(|f: &mut std::fmt::Formatter<'_>| spec.fmt_with(item, f, &fmt_item))
+                            ),
+                        )
+                    }
+                    (None, Some(spec)) => spec.fmt_with(item, f, &fmt_item),
+                    (None, None) => fmt_item(item, f),
+                }
+                .map_err(|_| state.fail(JoinError::element(*index)))?;
+                state.elements += 1;
+            }
+            Entry::Elided { count, .. } => {
+                if let Some((before, after)) = options.elision_marker.split_once("{}") {
+                    f.write_str(before)?;
+                    write!(f, "{}", count)?;
+                    f.write_str(after)?;
+                } else {
+                    f.write_str(&options.elision_marker)?;
+                }
+                state.elided += count;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Takes up to `n` elements from the back of an iterator, in the order of the
+/// iterator. See [`Joiner::limit_ends()`].
+type TakeBack<I> = fn(&mut I, usize) -> std::collections::VecDeque<<I as std::iter::Iterator>::Item>;
+
+fn take_back<I>(iter: &mut I, n: usize) -> std::collections::VecDeque<I::Item>
+where
+    I: std::iter::DoubleEndedIterator,
+{
+    let mut items = std::collections::VecDeque::with_capacity(n);
+    for item in iter.by_ref().rev().take(n) {
+        items.push_front(item);
+    }
+    items
+}
+
+/// Iterator-facade that applies a [`Limit`] to an iterator, yielding
+/// [`Entry::Elided`] in place of the skipped elements.
+struct Entries<I>
+where
+    I: std::iter::Iterator,
+{
+    iter: I,
+    limit: Option<Limit>,
+    take_back: Option<TakeBack<I>>,
+    position: usize,
+    tail: Option<std::collections::VecDeque<I::Item>>,
+}
+
+impl<I> Entries<I>
+where
+    I: std::iter::Iterator,
+{
+    #[inline]
+    fn new(iter: I, limit: Option<Limit>, take_back: Option<TakeBack<I>>) -> Self {
+        Self {
+            iter,
+            limit,
+            take_back,
+            position: 0,
+            tail: None,
+        }
+    }
+
+    /// Skips all but the last `tail` remaining elements and returns how many
+    /// elements were skipped. A single element is never skipped, since the
+    /// elision marker would be written in its place.
+    ///
+    /// If the size of the iterator is known the elements are skipped using
+    /// [`Iterator::nth()`]. Otherwise the last `tail` elements are taken from
+    /// the back if the iterator is a [`DoubleEndedIterator`], or else kept in
+    /// a buffer while the iterator is drained.
+    fn skip_middle(&mut self, tail: usize) -> usize {
+        let (lower, upper) = self.iter.size_hint();
+        if upper == Some(lower) {
+            let skipped = match lower.saturating_sub(tail) {
+                1 => 0,
+                skipped => skipped,
+            };
+            if skipped > 0 {
+                self.iter.nth(skipped - 1);
+            }
+            return skipped;
+        }
+
+        if let Some(take_back) = self.take_back {
+            let mut buffer = take_back(&mut self.iter, tail);
+            let skipped = match self.iter.next() {
+                Some(item) => match self.iter.by_ref().count() {
+                    0 => {
+                        buffer.push_front(item);
+                        0
+                    }
+                    count => count + 1,
+                },
+                None => 0,
+            };
+            self.tail = Some(buffer);
+            return skipped;
+        }
+
+        let mut buffer = std::collections::VecDeque::with_capacity(tail + 1);
+        let mut skipped = 0;
+        for item in self.iter.by_ref() {
+            if buffer.len() > tail {
+                buffer.pop_front();
+                skipped += 1;
+            }
+            buffer.push_back(item);
+        }
+        if skipped > 0 {
+            buffer.pop_front();
+            skipped += 1;
+        }
+        self.tail = Some(buffer);
+        skipped
+    }
+}
+
+impl<I> std::iter::Iterator for Entries<I>
+where
+    I: std::iter::Iterator,
+{
+    type Item = Entry<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.position;
+
+        if let Some(tail) = &mut self.tail {
+            let item = tail.pop_front()?;
+            self.position += 1;
+            return Some(Entry::Item { index, item });
+        }
+
+        if let Some(limit) = self.limit {
+            if index == limit.head {
+                self.limit = None;
+                let count = self.skip_middle(limit.tail);
+                if count > 0 {
+                    self.position += count;
+                    return Some(Entry::Elided { index, count });
+                }
+                return self.next();
+            }
+        }
+
+        let item = self.iter.next()?;
+        self.position += 1;
+        Some(Entry::Item { index, item })
+    }
+}
+
+/// [`std::fmt::Display`] adapter that calls a closure every time it is
+/// formatted.
+struct FmtFn<F>(F)
+where
+    F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+
+impl<F> std::fmt::Display for FmtFn<F>
+where
+    F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// [`Separator`] adapter that ends the line after the separator, dropping its
+/// trailing whitespace, or for `leading` separators starts a new line before
+/// every separator but the first. This is used for `{:#?}`.
+struct LineSep<'a, S> {
+    sep: &'a S,
+    leading: bool,
+}
+
+impl<T, S> Separator<T> for LineSep<'_, S>
+where
+    S: Separator<T>,
+{
+    fn fmt_sep(
+        &self,
+        index: usize,
+        prev: Option<&T>,
+        next: Option<&T>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        if self.leading {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            return self.sep.fmt_sep(index, prev, next, f);
+        }
+        // The separator is written twice: once to find where its trailing
+        // whitespace starts and once to write everything before that.
+        let sep = FmtFn(|f: &mut std::fmt::Formatter<'_>| self.sep.fmt_sep(index, prev, next, f));
+        let mut trimmed = TrimmedLen::default();
+        std::fmt::Write::write_fmt(&mut trimmed, format_args!("{}", sep))?;
+        let mut writer = BudgetWriter::new(&mut *f, Budget::Bytes(trimmed.trimmed));
+        match std::fmt::Write::write_fmt(&mut writer, format_args!("{}", sep)) {
+            Err(_) if writer.exhausted => {}
+            result => result?,
+        }
+        f.write_str("\n")
+    }
+}
+
+/// [`std::fmt::Write`] that counts the bytes written up to the end of the last
+/// non-whitespace character.
+#[derive(Default)]
+struct TrimmedLen {
+    len: usize,
+    trimmed: usize,
+}
+
+impl std::fmt::Write for TrimmedLen {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let trimmed = s.trim_end();
+        if !trimmed.is_empty() {
+            self.trimmed = self.len + trimmed.len();
+        }
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+/// [`std::fmt::Write`] adapter that writes `indent` at the start of every
+/// line, like the one used by [`std::fmt::Formatter::debug_list()`] for
+/// `{:#?}`.
+struct PadAdapter<'a, W>
+where
+    W: std::fmt::Write,
+{
+    inner: W,
+    indent: &'a str,
+    on_newline: bool,
+    skip_blank: bool,
+}
+
+impl<'a, W> PadAdapter<'a, W>
+where
+    W: std::fmt::Write,
+{
+    #[inline]
+    fn new(inner: W, indent: &'a str) -> Self {
+        Self {
+            inner,
+            indent,
+            on_newline: true,
+            skip_blank: false,
+        }
+    }
+
+    /// Create a [`PadAdapter`] that doesn't indent the first line.
+    #[inline]
+    fn hanging(inner: W, indent: &'a str) -> Self {
+        Self {
+            inner,
+            indent,
+            on_newline: false,
+            skip_blank: false,
+        }
+    }
+
+    /// Don't indent empty lines, unlike [`std::fmt::Formatter::debug_list()`].
+    /// This is used for [`Joiner::indent()`].
+    #[inline]
+    fn skip_blank_lines(mut self) -> Self {
+        self.skip_blank = true;
+        self
+    }
+}
+
+impl<W> std::fmt::Write for PadAdapter<'_, W>
+where
+    W: std::fmt::Write,
+{
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if self.on_newline && !(self.skip_blank && line == "\n") {
+                self.inner.write_str(self.indent)?;
+            }
+            self.on_newline = line.ends_with('\n');
+            self.inner.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// [`std::fmt::Display`] adapter that calls a closure the first time it is
+/// formatted.
+struct FmtOnce<F>(std::cell::Cell<Option<F>>)
+where
+    F: FnOnce(&mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+
+impl<F> FmtOnce<F>
+where
+    F: FnOnce(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn new(func: F) -> Self {
+        Self(std::cell::Cell::new(Some(func)))
+    }
+}
+
+impl<F> std::fmt::Display for FmtOnce<F>
+where
+    F: FnOnce(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.take() {
+            Some(func) => func(f),
+            None => Ok(()),
+        }
+    }
+}
+
+// =============================================================================
+//      struct FormatSpec
+// =============================================================================
+
+/// Format flags that are applied to every element of a [`Joiner`], the same
+/// flags that can be given in a format string.
+///
+/// See [`Joiner::element_format()`].
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{FormatSpec, Join};
+///
+/// // same as format!("{:>+6.2}", value) for every element
+/// let spec = FormatSpec::new()
+///     .width(6)
+///     .align(std::fmt::Alignment::Right)
+///     .sign_plus(true)
+///     .precision(2);
+///
+/// assert_eq!([1.0, -0.5].join(",").element_format(spec).into_string(), " +1.00, -0.50");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatSpec {
+    width: Option<usize>,
+    fill: char,
+    align: Option<std::fmt::Alignment>,
+    precision: Option<usize>,
+    sign_plus: bool,
+    alternate: bool,
+    zero_pad: bool,
+}
+
+impl Default for FormatSpec {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatSpec {
+    /// Create a [`FormatSpec`] without any flags, same as `{}`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            width: None,
+            fill: ' ',
+            align: None,
+            precision: None,
+            sign_plus: false,
+            alternate: false,
+            zero_pad: false,
+        }
+    }
+
+    /// Create a [`FormatSpec`] with the flags of `f`.
+    #[inline]
+    pub fn from_formatter(f: &std::fmt::Formatter<'_>) -> Self {
+        Self {
+            width: f.width(),
+            fill: f.fill(),
+            align: f.align(),
+            precision: f.precision(),
+            sign_plus: f.sign_plus(),
+            alternate: f.alternate(),
+            zero_pad: f.sign_aware_zero_pad(),
+        }
+    }
+
+    /// Minimum width of the element.
+    #[inline]
+    pub const fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Character used for padding to the [width](FormatSpec::width()). Like in
+    /// format strings this is only used if an
+    /// [alignment](FormatSpec::align()) is given, too.
+    #[inline]
+    pub const fn fill(mut self, fill: char) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Alignment of the element within the [width](FormatSpec::width()).
+    #[inline]
+    pub const fn align(mut self, align: std::fmt::Alignment) -> Self {
+        self.align = Some(align);
+        self
+    }
+
+    /// Precision of the element, e.g. the number of decimal places of floats
+    /// or the maximum length of strings.
+    #[inline]
+    pub const fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Always print the sign of numbers (`+` flag).
+    #[inline]
+    pub const fn sign_plus(mut self, sign_plus: bool) -> Self {
+        self.sign_plus = sign_plus;
+        self
+    }
+
+    /// Use the alternate form (`#` flag).
+    #[inline]
+    pub const fn alternate(mut self, alternate: bool) -> Self {
+        self.alternate = alternate;
+        self
+    }
+
+    /// Pad numbers with zeros after the sign (`0` flag).
+    #[inline]
+    pub const fn zero_pad(mut self, zero_pad: bool) -> Self {
+        self.zero_pad = zero_pad;
+        self
+    }
+
+    /// Formats `value` using `fmt` with the flags of this [`FormatSpec`].
+    ///
+    /// There is no stable way to create a [`std::fmt::Formatter`] with given
+    /// flags, so the flags are selected through format strings. Only the fill
+    /// character can't be passed at runtime, so padding with an alignment is
+    /// done manually after measuring the element.
+    fn fmt_with<T: ?Sized>(
+        &self,
+        value: &T,
+        f: &mut std::fmt::Formatter<'_>,
+        fmt: impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        let value = FmtWith { value, fmt };
+        match (self.align, self.width) {
+            (Some(align), Some(width)) if !self.zero_pad => {
+                let mut counter = CharCounter::default();
+                self.write_flags(&mut counter, &value, 0)?;
+                let (before, after) = padding(align, width.saturating_sub(counter.chars));
+                write_fill(&mut *f, self.fill, before)?;
+                self.write_flags(&mut *f, &value, 0)?;
+                write_fill(f, self.fill, after)
+            }
+            _ => self.write_flags(f, &value, self.width.unwrap_or(0)),
+        }
+    }
+
+    fn write_flags<W, V>(&self, mut writer: W, value: &V, width: usize) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+        V: std::fmt::Display,
+    {
+        match (self.sign_plus, self.alternate, self.zero_pad, self.precision) {
+            (false, false, false, None) => write!(writer, "{:w$}", value, w = width),
+            (false, false, false, Some(p)) => write!(writer, "{:w$.p$}", value, w = width, p = p),
+            (false, false, true, None) => write!(writer, "{:0w$}", value, w = width),
+            (false, false, true, Some(p)) => write!(writer, "{:0w$.p$}", value, w = width, p = p),
+            (false, true, false, None) => write!(writer, "{:#w$}", value, w = width),
+            (false, true, false, Some(p)) => write!(writer, "{:#w$.p$}", value, w = width, p = p),
+            (false, true, true, None) => write!(writer, "{:#0w$}", value, w = width),
+            (false, true, true, Some(p)) => write!(writer, "{:#0w$.p$}", value, w = width, p = p),
+            (true, false, false, None) => write!(writer, "{:+w$}", value, w = width),
+            (true, false, false, Some(p)) => write!(writer, "{:+w$.p$}", value, w = width, p = p),
+            (true, false, true, None) => write!(writer, "{:+0w$}", value, w = width),
+            (true, false, true, Some(p)) => write!(writer, "{:+0w$.p$}", value, w = width, p = p),
+            (true, true, false, None) => write!(writer, "{:+#w$}", value, w = width),
+            (true, true, false, Some(p)) => write!(writer, "{:+#w$.p$}", value, w = width, p = p),
+            (true, true, true, None) => write!(writer, "{:+#0w$}", value, w = width),
+            (true, true, true, Some(p)) => write!(writer, "{:+#0w$.p$}", value, w = width, p = p),
+        }
+    }
+}
+
+/// [`std::fmt::Display`] adapter that formats `value` using `fmt`, which may
+/// be the `fmt()` method of any formatting trait.
+struct FmtWith<'a, T: ?Sized, F> {
+    value: &'a T,
+    fmt: F,
+}
+
+impl<T: ?Sized, F> std::fmt::Display for FmtWith<'_, T, F>
+where
+    F: Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.fmt)(self.value, f)
+    }
+}
+
+/// [`std::fmt::Write`] that only counts the characters written to it.
+#[derive(Debug, Default)]
+struct CharCounter {
+    chars: usize,
+}
+
+impl std::fmt::Write for CharCounter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.chars += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Splits `padding` into the padding before and after a value.
+#[inline]
+fn padding(align: std::fmt::Alignment, padding: usize) -> (usize, usize) {
+    match align {
+        std::fmt::Alignment::Left => (0, padding),
+        std::fmt::Alignment::Right => (padding, 0),
+        std::fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+    }
+}
+
+/// Writes `fill` `count` times.
+fn write_fill<W: std::fmt::Write>(mut writer: W, fill: char, count: usize) -> std::fmt::Result {
+    for _ in 0..count {
+        writer.write_char(fill)?;
+    }
+    Ok(())
+}
+
+// =============================================================================
+//      struct Numbering
+// =============================================================================
+
+/// How the elements are numbered by [`Joiner::numbered()`].
+///
+/// The number is written using a template, in which the first `{}` is
+/// replaced by the number. The default is `"{}. "`. Numbers start at `1` per
+/// default. If the number of elements is exactly known the numbers are
+/// right-aligned to the widest one, unless [`Numbering::align()`] is turned
+/// off.
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{Join, NumberStyle, Numbering};
+///
+/// assert_eq!(
+///     (0..10).join(", ").numbered(Numbering::new(NumberStyle::Arabic).start(0).template("#{}:")).into_string(),
+///     "#0:0, #1:1, #2:2, #3:3, #4:4, #5:5, #6:6, #7:7, #8:8, #9:9"
+/// );
+/// assert_eq!(
+///     (0..10).join(", ").limit_ends(1, 1).numbered(Numbering::new(NumberStyle::ZeroPadded)).into_string(),
+///     "01. 0, …, 10. 9"
+/// );
+/// assert_eq!(
+///     ["x", "y"].join(' ').numbered(Numbering::new(NumberStyle::LowerRoman).start(8).template("({})")).into_string(),
+///     "(viii)x (  ix)y"
+/// );
+/// assert_eq!(
+///     ["x", "y"].join(' ').numbered(Numbering::new(NumberStyle::LowerRoman).start(8).template("({})").align(false)).into_string(),
+///     "(viii)x (ix)y"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Numbering {
+    style: NumberStyle,
+    start: usize,
+    template: std::borrow::Cow<'static, str>,
+    align: bool,
+}
+
+/// The way numbers are written by [`Joiner::numbered()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberStyle {
+    /// `1`, `2`, `3`, …
+    #[default]
+    Arabic,
+
+    /// `01`, `02`, `03`, … padded with zeros to the widest number if the
+    /// number of elements is exactly known.
+    ZeroPadded,
+
+    /// `a`, `b`, …, `z`, `aa`, `ab`, …
+    LowerAlpha,
+
+    /// `A`, `B`, …, `Z`, `AA`, `AB`, …
+    UpperAlpha,
+
+    /// `i`, `ii`, `iii`, `iv`, … Numbers above `3999` are written as arabic
+    /// numbers.
+    LowerRoman,
+
+    /// `I`, `II`, `III`, `IV`, … Numbers above `3999` are written as arabic
+    /// numbers.
+    UpperRoman,
+}
+
+impl Default for Numbering {
+    #[inline]
+    fn default() -> Self {
+        Self::new(NumberStyle::default())
+    }
+}
+
+impl Numbering {
+    /// Create a [`Numbering`] object with the given style.
+    #[inline]
+    pub fn new(style: NumberStyle) -> Self {
+        Self {
+            style,
+            start: 1,
+            template: std::borrow::Cow::Borrowed("{}. "),
+            align: true,
+        }
+    }
+
+    /// The number of the first element. Defaults to `1`.
+    ///
+    /// Alphabetic and roman numbers start at `1`, so for them `0` is written
+    /// as an arabic number.
+    #[inline]
+    pub fn start(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Set the template in which the first `{}` is replaced by the number.
+    /// Defaults to `"{}. "`.
+    #[inline]
+    pub fn template(mut self, template: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Set whether the numbers are right-aligned if the number of elements is
+    /// exactly known. Defaults to `true`. Numbers of the style
+    /// [`NumberStyle::ZeroPadded`] are always padded.
+    #[inline]
+    pub fn align(mut self, align: bool) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Returns the width of the widest of `count` numbers, or `0` if the
+    /// numbers aren't padded.
+    fn width(&self, count: usize) -> usize {
+        if count == 0 || !(self.align || self.style == NumberStyle::ZeroPadded) {
+            return 0;
+        }
+        let last = self.start.saturating_add(count - 1);
+        match self.style {
+            // Roman numbers don't get wider monotonically. A number has one
+            // `M` per thousand and at most 12 more letters (888), so numbers
+            // two thousands or more below the last one are narrower than the
+            // x888 in the thousand below the last one. Numbers above
+            // `ROMAN_MAX` are arabic, so the last one is the widest of them.
+            NumberStyle::LowerRoman | NumberStyle::UpperRoman => {
+                let roman_last = last.min(ROMAN_MAX);
+                (self.start.max(roman_last.saturating_sub(1999))..=roman_last)
+                    .map(|number| self.number_width(number))
+                    .max()
+                    .unwrap_or(0)
+                    .max(self.number_width(last))
+            }
+            _ => self.number_width(last),
+        }
+    }
+
+    fn number_width(&self, number: usize) -> usize {
+        match self.style {
+            NumberStyle::LowerRoman | NumberStyle::UpperRoman if (1..=ROMAN_MAX).contains(&number) => {
+                roman_width(number)
+            }
+            _ => {
+                let mut counter = CharCounter::default();
+                let _ = self.write_number(&mut counter, number);
+                counter.chars
+            }
+        }
+    }
+
+    /// Writes the template with the number of the element at `index`, padded
+    /// to `width`.
+    fn write_label(
+        &self,
+        mut writer: impl std::fmt::Write,
+        index: usize,
+        width: usize,
+    ) -> std::fmt::Result {
+        let number = self.start.saturating_add(index);
+        let Some((before, after)) = self.template.split_once("{}") else {
+            return writer.write_str(&self.template);
+        };
+        writer.write_str(before)?;
+        if width > 0 {
+            let fill = match self.style {
+                NumberStyle::ZeroPadded => '0',
+                _ => ' ',
+            };
+            write_fill(&mut writer, fill, width.saturating_sub(self.number_width(number)))?;
+        }
+        self.write_number(&mut writer, number)?;
+        writer.write_str(after)
+    }
+
+    fn write_number(&self, mut writer: impl std::fmt::Write, number: usize) -> std::fmt::Result {
+        match self.style {
+            _ if number == 0 => writer.write_char('0'),
+            NumberStyle::LowerRoman | NumberStyle::UpperRoman if number > ROMAN_MAX => {
+                write!(writer, "{}", number)
+            }
+            NumberStyle::Arabic | NumberStyle::ZeroPadded => write!(writer, "{}", number),
+            NumberStyle::LowerAlpha => write_alpha(writer, number, b'a'),
+            NumberStyle::UpperAlpha => write_alpha(writer, number, b'A'),
+            NumberStyle::LowerRoman => write_roman(writer, number, ROMAN_LOWER),
+            NumberStyle::UpperRoman => write_roman(writer, number, ROMAN_UPPER),
+        }
+    }
+}
+
+/// Writes `number` (starting at `1`) in bijective base 26, like spreadsheet
+/// columns are named.
+fn write_alpha(mut writer: impl std::fmt::Write, mut number: usize, first: u8) -> std::fmt::Result {
+    // usize::MAX needs 14 letters
+    let mut buffer = [0u8; 16];
+    let mut pos = buffer.len();
+    while number > 0 {
+        number -= 1;
+        pos -= 1;
+        buffer[pos] = first + (number % 26) as u8;
+        number /= 26;
+    }
+    for &letter in &buffer[pos..] {
+        writer.write_char(letter as char)?;
+    }
+    Ok(())
+}
+
+/// The largest number that is written as a roman numeral. Larger numbers
+/// would need one `M` per thousand.
+const ROMAN_MAX: usize = 3999;
+
+const ROMAN_LOWER: [(usize, &str); 13] = [
+    (1000, "m"), (900, "cm"), (500, "d"), (400, "cd"), (100, "c"), (90, "xc"),
+    (50, "l"), (40, "xl"), (10, "x"), (9, "ix"), (5, "v"), (4, "iv"), (1, "i"),
+];
+
+const ROMAN_UPPER: [(usize, &str); 13] = [
+    (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"),
+    (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+];
+
+/// Returns the width of `number` as a roman numeral without writing it.
+#[inline]
+fn roman_width(number: usize) -> usize {
+    // the widths of the digits 0 to 9, e.g. `viii` for 8
+    const WIDTHS: [usize; 10] = [0, 1, 2, 3, 2, 1, 2, 3, 4, 2];
+    number / 1000 + WIDTHS[number / 100 % 10] + WIDTHS[number / 10 % 10] + WIDTHS[number % 10]
+}
+
+/// Writes `number`, which is at most [`ROMAN_MAX`], as a roman numeral.
+fn write_roman(
+    mut writer: impl std::fmt::Write,
+    mut number: usize,
+    digits: [(usize, &str); 13],
+) -> std::fmt::Result {
+    for (value, digit) in digits {
+        while number >= value {
+            writer.write_str(digit)?;
+            number -= value;
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+//      struct Chunks
+// =============================================================================
+
+/// How the elements are grouped by [`Joiner::chunked()`].
+///
+/// Groups of `size` elements are separated by the group separator instead of
+/// the separator. Per default the groups are counted from the first element.
+/// With [`ChunkAlign::End`] they are counted from the last element instead,
+/// so that the first group is the short one. This needs the number of
+/// elements up front. If the iterator doesn't report its exact size, like
+/// [`str::chars()`], the elements are buffered in order to count them.
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{ChunkAlign, Chunks, Join};
+///
+/// let digits = [1, 2, 3, 4, 5, 6, 7];
+/// assert_eq!(digits.join("").chunked(Chunks::new(3, "_")).into_string(), "123_456_7");
+/// assert_eq!(
+///     digits.join("").chunked(Chunks::new(3, "_").align(ChunkAlign::End)).into_string(),
+///     "1_234_567"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunks {
+    size: usize,
+    sep: std::borrow::Cow<'static, str>,
+    align: ChunkAlign,
+}
+
+/// Where the groups of [`Chunks`] are counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkAlign {
+    /// The first group is full and the last one may be short.
+    #[default]
+    Start,
+
+    /// The last group is full and the first one may be short.
+    End,
+}
+
+impl Chunks {
+    /// Create a [`Chunks`] object that writes `sep` between groups of `size`
+    /// elements. If `size` is `0` the elements aren't grouped.
+    #[inline]
+    pub fn new(size: usize, sep: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self {
+            size,
+            sep: sep.into(),
+            align: ChunkAlign::default(),
+        }
+    }
+
+    /// Set where the groups are counted from. Defaults to
+    /// [`ChunkAlign::Start`].
+    #[inline]
+    pub fn align(mut self, align: ChunkAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Returns how many elements are missing from the first group for `len`
+    /// elements.
+    fn offset(&self, len: Option<usize>) -> usize {
+        match (self.align, len) {
+            (ChunkAlign::End, Some(len)) if self.size > 0 => (self.size - len % self.size) % self.size,
+            _ => 0,
+        }
+    }
+
+    /// Returns whether a group starts with the element at `index`.
+    // usize::is_multiple_of() needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    #[inline]
+    fn is_boundary(&self, index: usize, offset: usize) -> bool {
+        self.size > 0 && (index + offset) % self.size == 0
+    }
+}
+
+// =============================================================================
+//      struct Ranges
+// =============================================================================
+
+/// How consecutive integers are collapsed by [`Join::join_ranges()`].
+///
+/// Runs of at least [`Ranges::min_run()`] consecutive integers are written as
+/// the first integer, the range marker and the last integer. The marker
+/// defaults to `"-"` and the minimum run length to `2`. Per default the
+/// integers are taken in the order of the iterator, so only runs that are
+/// already in ascending order are collapsed. Use [`Ranges::sort()`] for
+/// unsorted input.
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{Join, Ranges};
+///
+/// assert_eq!([1, 2, 3, 5, 7, 8].join_ranges(", ", Ranges::default()).into_string(), "1-3, 5, 7-8");
+/// assert_eq!(
+///     [1, 2, 3, 5, 7, 8].join_ranges(", ", Ranges::new("..=").min_run(3)).into_string(),
+///     "1..=3, 5, 7, 8"
+/// );
+/// assert_eq!(
+///     [8, 3, 1, 7, 2, 3].join_ranges(',', Ranges::default().sort(true)).into_string(),
+///     "1-3,7-8"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranges {
+    marker: std::borrow::Cow<'static, str>,
+    min_run: usize,
+    sort: bool,
+}
+
+impl Default for Ranges {
+    #[inline]
+    fn default() -> Self {
+        Self::new("-")
+    }
+}
+
+impl Ranges {
+    /// Create a [`Ranges`] object with the given range marker.
+    #[inline]
+    pub fn new(marker: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self {
+            marker: marker.into(),
+            min_run: 2,
+            sort: false,
+        }
+    }
+
+    /// The minimum number of consecutive integers that are collapsed into a
+    /// range. Shorter runs are written as individual integers. Defaults to
+    /// `2`.
+    #[inline]
+    pub fn min_run(mut self, min_run: usize) -> Self {
+        self.min_run = min_run;
+        self
+    }
+
+    /// Set whether the integers are sorted and deduplicated before they are
+    /// collapsed. Defaults to `false`.
+    ///
+    /// Sorting collects the elements into a [`Vec`] when calling
+    /// [`Join::join_ranges()`].
+    #[inline]
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+}
+
+/// Integer types that can be collapsed into ranges by [`Join::join_ranges()`].
+///
+/// This is implemented for all primitive integer types.
+pub trait Step: Copy + Ord {
+    /// Returns the next larger value, or [`None`] on overflow.
+    fn successor(self) -> Option<Self>;
+}
+
+macro_rules! impl_step {
+    ($($type:ty)*) => {
+        $(
+            impl Step for $type {
+                #[inline]
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_step!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+// =============================================================================
+//      struct Budget
+// =============================================================================
+
+/// Limit for the size of the output of a [`Joiner`].
+///
+/// See [`Joiner::max_bytes()`] and [`Joiner::max_chars()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Budget {
+    Bytes(usize),
+    Chars(usize),
+}
+
+/// [`std::fmt::Write`] adapter that writes at most the given [`Budget`] and
+/// fails once it is exceeded.
+///
+/// Output is only cut at UTF-8 character boundaries.
+struct BudgetWriter<W: std::fmt::Write> {
+    inner: W,
+    budget: Budget,
+    exhausted: bool,
+}
+
+impl<W: std::fmt::Write> BudgetWriter<W> {
+    #[inline]
+    fn new(inner: W, budget: Budget) -> Self {
+        Self {
+            inner,
+            budget,
+            exhausted: false,
+        }
+    }
+}
+
+impl<W: std::fmt::Write> std::fmt::Write for BudgetWriter<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.exhausted {
+            return Err(std::fmt::Error);
+        }
+
+        let end = match &mut self.budget {
+            Budget::Bytes(remaining) => {
+                if s.len() <= *remaining {
+                    *remaining -= s.len();
+                    return self.inner.write_str(s);
+                }
+                let mut end = *remaining;
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                end
+            }
+            Budget::Chars(remaining) => match s.char_indices().nth(*remaining) {
+                Some((end, _)) => end,
+                None => {
+                    *remaining -= s.chars().count();
+                    return self.inner.write_str(s);
+                }
+            },
+        };
+
+        self.exhausted = true;
+        self.inner.write_str(&s[..end])?;
+        Err(std::fmt::Error)
+    }
+}
+
+// =============================================================================
+//      struct OnceJoiner
+// =============================================================================
+
+/// A [`Joiner`] that consumes its iterator when it is formatted the first
+/// time.
+///
+/// This implements [`std::fmt::Display`] and [`std::fmt::Debug`] even if the
+/// iterator doesn't implement [`Clone`]. What happens when it is formatted a
+/// second time is controlled by [`OnceJoiner::on_reuse()`].
+///
+/// This is returned by [`Joiner::once()`].
+///
+/// # Examples
+///
+/// ```
+/// use join_string::Join;
+///
+/// let joiner = "foo bar baz".split_whitespace().map(str::to_uppercase).join(", ").once();
+/// assert_eq!(format!("{}", joiner), "FOO, BAR, BAZ");
+/// assert_eq!(format!("{}", joiner), "");
+/// ```
+pub struct OnceJoiner<I, S, F = DefaultFormat>
+where
+    I: std::iter::Iterator,
+{
+    joiner: std::cell::Cell<Option<Joiner<I, S, F>>>,
+    state: std::cell::Cell<JoinState>,
+    reuse: ReusePolicy,
+}
+
+impl<I, S, F> OnceJoiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    /// Create a [`OnceJoiner`] object.
+    #[inline]
+    pub fn new(joiner: Joiner<I, S, F>) -> Self {
+        Self {
+            joiner: std::cell::Cell::new(Some(joiner)),
+            state: std::cell::Cell::new(JoinState::default()),
+            reuse: ReusePolicy::default(),
+        }
+    }
+
+    /// Set what happens when the [`OnceJoiner`] is formatted a second time.
+    /// Defaults to [`ReusePolicy::Empty`].
+    #[inline]
+    pub fn on_reuse(mut self, policy: ReusePolicy) -> Self {
+        self.reuse = policy;
+        self
+    }
+
+    /// Whether the iterator was already consumed.
+    #[inline]
+    pub fn is_consumed(&self) -> bool {
+        let joiner = self.joiner.take();
+        let consumed = joiner.is_none();
+        self.joiner.set(joiner);
+        consumed
+    }
+
+    /// Formats the elements using `fmt_item`, which gets the element
+    /// formatter of the [`Joiner`] as its first argument.
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        fmt_item: impl Fn(&F, &I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+        debug: bool,
+    ) -> std::fmt::Result {
+        let Some(joiner) = self.joiner.take() else {
+            return match self.reuse {
+                ReusePolicy::Empty => Ok(()),
+                ReusePolicy::Error => Err(std::fmt::Error),
+                ReusePolicy::Panic => panic!("OnceJoiner was formatted a second time"),
+            };
+        };
+        let fmt = &joiner.fmt;
+        let fmt_item = |item: &I::Item, f: &mut std::fmt::Formatter<'_>| fmt_item(fmt, item, f);
+        let mut state = self.state.get();
+        let result = if debug && (joiner.options.debug_list || f.alternate()) {
+            fmt_debug_layout(
+                joiner.iter,
+                joiner.take_back,
+                &joiner.sep,
+                &joiner.options,
+                f,
+                fmt_item,
+                &mut state,
+            )
+        } else {
+            fmt_joined_once(
+                joiner.iter,
+                joiner.take_back,
+                &joiner.sep,
+                &joiner.options,
+                f,
+                fmt_item,
+                &mut state,
+            )
+        };
+        self.state.set(state);
+        result
+    }
+}
+
+/// Like [`fmt_joined()`], but for an iterator that can only be consumed once.
+/// Padding the output to the width of `f` therefore requires buffering it.
+fn fmt_joined_once<I, S>(
+    iter: I,
+    take_back: Option<TakeBack<I>>,
+    sep: &S,
+    options: &JoinOptions,
+    f: &mut std::fmt::Formatter<'_>,
+    fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    state: &mut JoinState,
+) -> std::fmt::Result
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+{
+    let Some(width) = f.width() else {
+        return fmt_joined(iter, take_back, sep, options, f, fmt_item, state);
+    };
+    let fmt_item = padded_fmt_item(f, options, fmt_item);
+    let mut buffer = String::new();
+    std::fmt::Write::write_fmt(
+        &mut buffer,
+        format_args!(
+            "{}",
+            FmtOnce::new(|f: &mut std::fmt::Formatter<'_>| fmt_joined(
+                iter, take_back, sep, options, f, fmt_item, state,
+            ))
+        ),
+    )?;
+    let chars = buffer.chars().count();
+    write_padded(f, width, chars, |f| f.write_str(&buffer))
+}
+
+impl<I, S, F> std::fmt::Display for OnceJoiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+    F: DisplayElement<I::Item>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, F::fmt_display, false)
+    }
+}
+
+impl<I, S, F> std::fmt::Debug for OnceJoiner<I, S, F>
+where
+    I: std::iter::Iterator,
+    S: Separator<I::Item>,
+    F: DebugElement<I::Item>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, F::fmt_debug, true)
+    }
+}
+
+/// What happens when a [`OnceJoiner`] is formatted a second time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReusePolicy {
+    /// Write nothing.
+    #[default]
+    Empty,
+
+    /// Return a [`std::fmt::Error`]. Note that this makes e.g. [`format!()`]
+    /// panic.
+    Error,
+
+    /// Panic.
+    Panic,
+}
+
+// =============================================================================
+//      struct JoinerRef
+// =============================================================================
+
+/// Helper struct that borrows a collection and captures a separator for
+/// joining the elements of the collection any number of times.
+///
+/// Every time it is formatted [`IntoIterator::into_iter()`] is called on the
+/// reference to the collection, so unlike [`Joiner`] this implements
+/// [`std::fmt::Display`] even if the iterator of the collection doesn't
+/// implement [`Clone`].
+///
+/// This is returned by [`Join::join_by_ref()`].
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{Join, JoinerRef};
+///
+/// struct Report<'a> {
+///     tags: JoinerRef<'a, Vec<String>, &'static str>,
+/// }
+///
+/// let tags = vec!["foo".to_owned(), "bar".to_owned()];
+/// let report = Report {
+///     tags: tags.join_by_ref(", ").wrap("[", "]"),
+/// };
+/// assert_eq!(report.tags.to_string(), "[foo, bar]");
+/// assert_eq!(report.tags.to_string(), "[foo, bar]");
+/// assert_eq!(tags.len(), 2);
+/// ```
+pub struct JoinerRef<'a, C, S>
+where
+    C: ?Sized,
+{
+    collection: &'a C,
+    sep: S,
+    options: JoinOptions,
+}
+
+impl<C, S> Clone for JoinerRef<'_, C, S>
+where
+    C: ?Sized,
+    S: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            collection: self.collection,
+            sep: self.sep.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl<'a, C, S> JoinerRef<'a, C, S>
+where
+    C: ?Sized,
+{
+    /// Create a [`JoinerRef`] object.
+    #[inline]
+    pub fn new(collection: &'a C, sep: S) -> Self {
+        Self {
+            collection,
+            sep,
+            options: JoinOptions::default(),
+        }
+    }
+
+    joiner_options!();
+    debug_options!();
+
+    /// Write at most the first `head` and the last `tail` elements. If there
+    /// are at least two more elements the
+    /// [elision marker](Joiner::elision_marker()) is written in place of the
+    /// elements in between.
+    ///
+    /// If the size of the iterator is exactly known (see [`Joiner::limit()`])
+    /// the elements in between are skipped. Otherwise the iterator is drained
+    /// while buffering the last `tail` elements.
+    #[inline]
+    pub fn limit_ends(mut self, head: usize, tail: usize) -> Self {
+        self.options.limit = Some(Limit { head, tail });
+        self
+    }
+
+    /// Returns the joined collection.
+    #[inline]
+    pub fn collection(&self) -> &'a C {
+        self.collection
+    }
+}
+
+impl<'a, C, S> JoinerRef<'a, C, S>
+where
+    C: ?Sized,
+    &'a C: std::iter::IntoIterator,
+    <&'a C as std::iter::IntoIterator>::Item: std::fmt::Display,
+    S: Separator<<&'a C as std::iter::IntoIterator>::Item>,
+{
+    /// Returns the joined elements as a new [`String`].
+    ///
+    /// This is the same as [`Joiner::into_string()`] on a fresh iterator over
+    /// the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let values = vec![1, 2, 3];
+    /// assert_eq!(values.join_by_ref(", ").into_string(), "1, 2, 3");
+    /// assert_eq!(values.len(), 3);
+    /// ```
+    #[inline]
+    pub fn into_string(self) -> String {
+        Joiner {
+            iter: self.collection.into_iter(),
+            sep: self.sep,
+            fmt: DefaultFormat,
+            options: self.options,
+            take_back: None,
+        }
+        .into_string()
+    }
+
+    /// Writes the joined elements into a [`std::fmt::Write`].
+    #[inline]
+    pub fn write_fmt<W: std::fmt::Write>(&self, mut writer: W) -> std::fmt::Result {
+        write!(writer, "{}", self)
+    }
+
+    /// Writes the joined elements into a [`std::io::Write`].
+    #[inline]
+    pub fn write_io<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+}
+
+impl<'a, C, S> std::fmt::Display for JoinerRef<'a, C, S>
+where
+    C: ?Sized,
+    &'a C: std::iter::IntoIterator,
+    <&'a C as std::iter::IntoIterator>::Item: std::fmt::Display,
+    S: Separator<<&'a C as std::iter::IntoIterator>::Item>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_padded(
+            || self.collection.into_iter(),
+            None,
+            &self.sep,
+            &self.options,
+            f,
+            std::fmt::Display::fmt,
+        )
+    }
+}
+
+impl<'a, C, S> std::fmt::Debug for JoinerRef<'a, C, S>
+where
+    C: ?Sized,
+    &'a C: std::iter::IntoIterator,
+    <&'a C as std::iter::IntoIterator>::Item: std::fmt::Debug,
+    S: Separator<<&'a C as std::iter::IntoIterator>::Item>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.options.debug_list || f.alternate() {
+            return fmt_debug_layout(
+                self.collection.into_iter(),
+                None,
+                &self.sep,
+                &self.options,
+                f,
+                std::fmt::Debug::fmt,
+                &mut JoinState::default(),
+            );
+        }
+        fmt_padded(
+            || self.collection.into_iter(),
+            None,
+            &self.sep,
+            &self.options,
+            f,
+            std::fmt::Debug::fmt,
+        )
+    }
+}
+
+// =============================================================================
+//      numeric formatting traits
+// =============================================================================
+
+/// Implements formatting traits besides [`std::fmt::Display`] and
+/// [`std::fmt::Debug`] for [`Joiner`] and [`JoinerRef`]. For these all flags
+/// apply to every element and the separator is written without flags.
+macro_rules! impl_fmt_traits {
+    ($($trait:ident),* $(,)?) => {
+        $(
+            impl<I, S> std::fmt::$trait for Joiner<I, S>
+            where
+                I: std::iter::Iterator,
+                S: Separator<I::Item>,
+                I::Item: std::fmt::$trait,
+                I: Clone,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    fmt_joined(
+                        self.iter.clone(),
+                        self.take_back,
+                        &self.sep,
+                        &self.options,
+                        f,
+                        std::fmt::$trait::fmt,
+                        &mut JoinState::default(),
+                    )
+                }
+            }
+
+            impl<'a, C, S> std::fmt::$trait for JoinerRef<'a, C, S>
+            where
+                C: ?Sized,
+                &'a C: std::iter::IntoIterator,
+                <&'a C as std::iter::IntoIterator>::Item: std::fmt::$trait,
+                S: Separator<<&'a C as std::iter::IntoIterator>::Item>,
+            {
+                #[inline]
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    fmt_joined(
+                        self.collection.into_iter(),
+                        None,
+                        &self.sep,
+                        &self.options,
+                        f,
+                        std::fmt::$trait::fmt,
+                        &mut JoinState::default(),
+                    )
+                }
+            }
+        )*
+    };
+}
+
+impl_fmt_traits!(LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp);
+
+// =============================================================================
+//      struct WriteStats
+// =============================================================================
+
+/// Statistics about the output written by [`Joiner::write_fmt_stats()`] and
+/// [`Joiner::write_io_stats()`].
+///
+/// Elements and separators are only counted if they were written
+/// completely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteStats {
+    /// Number of elements written.
+    pub elements: usize,
+
+    /// Number of separators written.
+    pub separators: usize,
+
+    /// Number of elements that where skipped because of [`Joiner::limit()`]
+    /// or [`Joiner::limit_ends()`].
+    pub elided: usize,
+
+    /// Number of bytes written, including prefix, suffix and markers.
+    pub bytes: usize,
+
+    /// Whether the output was truncated because of [`Joiner::max_bytes()`] or
+    /// [`Joiner::max_chars()`].
+    pub truncated: bool,
+}
+
+/// Error returned by [`Joiner::write_fmt_stats()`] and
+/// [`Joiner::write_io_stats()`], carrying the statistics up to the point where
+/// writing failed.
+#[derive(Debug)]
+pub struct WriteStatsError<E> {
+    error: E,
+    stats: WriteStats,
+}
+
+impl<E> WriteStatsError<E> {
+    /// The error that stopped writing.
+    #[inline]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// What was written before the error occurred.
+    #[inline]
+    pub fn stats(&self) -> &WriteStats {
+        &self.stats
+    }
+
+    /// Returns the error that stopped writing.
+    #[inline]
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for WriteStatsError<E> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for WriteStatsError<E> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<WriteStatsError<std::fmt::Error>> for std::fmt::Error {
+    #[inline]
+    fn from(value: WriteStatsError<std::fmt::Error>) -> Self {
+        value.error
+    }
+}
+
+impl From<WriteStatsError<std::io::Error>> for std::io::Error {
+    #[inline]
+    fn from(value: WriteStatsError<std::io::Error>) -> Self {
+        value.error
+    }
+}
+
+/// [`std::fmt::Write`] and [`std::io::Write`] adapter that counts the written
+/// bytes.
+struct CountingWriter<W> {
+    inner: W,
+    bytes: usize,
+}
+
+impl<W> CountingWriter<W> {
+    #[inline]
+    fn new(inner: W) -> Self {
+        Self { inner, bytes: 0 }
+    }
+}
+
+impl<W: std::fmt::Write> std::fmt::Write for CountingWriter<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_str(s)?;
+        self.bytes += s.len();
+        Ok(())
+    }
+}
+
+impl<W: std::io::Write> std::io::Write for CountingWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let count = self.inner.write(buf)?;
+        self.bytes += count;
+        Ok(count)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// =============================================================================
+//      struct TryJoiner
+// =============================================================================
+
+/// Helper struct that captures an iterator over [`Result`]s and a separator
+/// for later joining. Writing stops at the first error of the iterator.
+///
+/// This is returned by [`Join::try_join()`].
+pub struct TryJoiner<I, S>
+where
+    I: std::iter::Iterator,
+{
+    iter: I,
+    sep: S,
+    options: JoinOptions,
+}
+
+impl<I, S> TryJoiner<I, S>
+where
+    I: std::iter::Iterator,
+{
+    /// Create a [`TryJoiner`] object.
+    #[inline]
+    pub fn new(iter: I, sep: S) -> Self {
+        Self {
+            iter,
+            sep,
+            options: JoinOptions::default(),
+        }
+    }
+
+    joiner_options!();
+
+    /// Write at most the first `head` and the last `tail` elements. If there
+    /// are at least two more elements the
+    /// [elision marker](Joiner::elision_marker()) is written in place of the
+    /// elements in between.
+    ///
+    /// If the size of the iterator is exactly known (see [`Joiner::limit()`])
+    /// the elements in between are skipped. Otherwise the iterator is drained
+    /// while buffering the last `tail` elements.
+    #[inline]
+    pub fn limit_ends(mut self, head: usize, tail: usize) -> Self {
+        self.options.limit = Some(Limit { head, tail });
+        self
+    }
+}
+
+impl<I, S, T, E> TryJoiner<I, S>
+where
+    I: std::iter::Iterator<Item = Result<T, E>>,
+    S: Separator<T>,
+    T: std::fmt::Display,
+{
+    /// Consumes the backing iterator of a [`TryJoiner`] and returns the joined elements as a new [`String`].
+    ///
+    /// Stops at and returns the first error of the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::{Join, TryJoinError};
+    ///
+    /// assert_eq!(
+    ///     "1 2 3".split(' ').map(str::parse::<u32>).try_join(", ").into_string(),
+    ///     Ok("1, 2, 3".to_owned())
+    /// );
+    ///
+    /// assert!(matches!(
+    ///     "1 x 3".split(' ').map(str::parse::<u32>).try_join(", ").into_string(),
+    ///     Err(TryJoinError::Item(_))
+    /// ));
+    /// ```
+    pub fn into_string(self) -> Result<String, TryJoinError<E, JoinError>> {
+        let error = std::cell::Cell::new(None);
+        let mut buffer = String::new();
+        let result = self.into_joiner(&error).write_checked(UntilErrWriter {
+            writer: &mut buffer,
+            error: &error,
+        });
+        match error.take() {
+            Some(error) => Err(TryJoinError::Item(error)),
+            None => result.map(|_| buffer).map_err(TryJoinError::Write),
+        }
+    }
+
+    /// Consumes the backing iterator of a [`TryJoiner`] and writes the joined elements into a [`std::fmt::Write`].
+    ///
+    /// Stops at and returns the first error of the iterator. Neither the
+    /// separator in front of the error nor the suffix is written then, so
+    /// the partial output doesn't look complete.
+    pub fn write_fmt<W: std::fmt::Write>(self, writer: W) -> Result<(), TryJoinError<E, std::fmt::Error>> {
+        let error = std::cell::Cell::new(None);
+        let result = self.into_joiner(&error).write_fmt(UntilErrWriter { writer, error: &error });
+        match error.take() {
+            Some(error) => Err(TryJoinError::Item(error)),
+            None => result.map_err(TryJoinError::Write),
+        }
+    }
+
+    /// Consumes the backing iterator of a [`TryJoiner`] and writes the joined elements into a [`std::io::Write`].
+    ///
+    /// Stops at and returns the first error of the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    /// use std::io::BufRead;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let input = std::io::Cursor::new("foo\nbar\nbaz\n");
+    /// let mut output = Vec::new();
+    /// input.lines().try_join(", ").write_io(&mut output)?;
+    /// assert_eq!(output, b"foo, bar, baz");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_io<W: std::io::Write>(self, writer: W) -> Result<(), TryJoinError<E, std::io::Error>> {
+        let error = std::cell::Cell::new(None);
+        let result = self.into_joiner(&error).write_io(UntilErrWriter { writer, error: &error });
+        match error.take() {
+            Some(error) => Err(TryJoinError::Item(error)),
+            None => result.map_err(TryJoinError::Write),
+        }
+    }
+
+    #[inline]
+    fn into_joiner(self, error: &std::cell::Cell<Option<E>>) -> Joiner<UntilErr<'_, I, E>, S> {
+        Joiner {
+            iter: UntilErr {
+                iter: self.iter,
+                error,
+                done: false,
+            },
+            sep: self.sep,
+            fmt: DefaultFormat,
+            options: self.options,
+            take_back: None,
+        }
+    }
+}
+
+/// Writer-facade that fails all writes once [`UntilErr`] stored an error.
+///
+/// [`UntilErr`] can only end the iteration on an error, which looks like the
+/// regular end to the joiner. Failing the writes instead of ending normally
+/// prevents the last separator, the terminator and the suffix from being
+/// written, so partial output doesn't look complete.
+struct UntilErrWriter<'a, W, E> {
+    writer: W,
+    error: &'a std::cell::Cell<Option<E>>,
+}
+
+impl<W, E> UntilErrWriter<'_, W, E> {
+    #[inline]
+    fn failed(&self) -> bool {
+        let error = self.error.take();
+        let failed = error.is_some();
+        self.error.set(error);
+        failed
+    }
+}
+
+impl<W: std::fmt::Write, E> std::fmt::Write for UntilErrWriter<'_, W, E> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.failed() {
+            return Err(std::fmt::Error);
+        }
+        self.writer.write_str(s)
+    }
+}
+
+impl<W: std::io::Write, E> std::io::Write for UntilErrWriter<'_, W, E> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.failed() {
+            return Err(std::io::ErrorKind::Other.into());
+        }
+        self.writer.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Iterator-facade that yields the [`Ok`] values of an iterator over
+/// [`Result`]s and stops at the first [`Err`], which is stored in `error`.
+struct UntilErr<'a, I, E> {
+    iter: I,
+    error: &'a std::cell::Cell<Option<E>>,
+    done: bool,
+}
+
+impl<I, T, E> std::iter::Iterator for UntilErr<'_, I, E>
+where
+    I: std::iter::Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.iter.next()? {
+            Ok(item) => Some(item),
+            Err(error) => {
+                self.error.set(Some(error));
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The size is never exact, since any element might be an error.
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Error returned by the output methods of [`TryJoiner`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryJoinError<E, W> {
+    /// The iterator yielded an error.
+    Item(E),
+
+    /// Writing the output failed.
+    Write(W),
+}
+
+impl<E, W> std::fmt::Display for TryJoinError<E, W>
+where
+    E: std::fmt::Display,
+    W: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryJoinError::Item(error) => error.fmt(f),
+            TryJoinError::Write(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E, W> std::error::Error for TryJoinError<E, W>
+where
+    E: std::error::Error,
+    W: std::error::Error,
+{
+}
+
+impl From<TryJoinError<std::io::Error, std::io::Error>> for std::io::Error {
+    #[inline]
+    fn from(value: TryJoinError<std::io::Error, std::io::Error>) -> Self {
+        match value {
+            TryJoinError::Item(error) | TryJoinError::Write(error) => error,
+        }
+    }
+}
+
+// =============================================================================
+//      struct JoinError
+// =============================================================================
+
+/// Error returned by [`Joiner::try_into_string()`] if formatting an element,
+/// a separator or anything else that is written around them failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JoinError {
+    index: Option<usize>,
+    separator: bool,
+}
+
+impl JoinError {
+    #[inline]
+    fn element(index: usize) -> Self {
+        Self {
+            index: Some(index),
+            separator: false,
+        }
+    }
+
+    #[inline]
+    fn separator(index: usize) -> Self {
+        Self {
+            index: Some(index),
+            separator: true,
+        }
+    }
+
+    /// The error isn't tied to an element or a separator, e.g. writing the
+    /// prefix, the suffix or a marker failed.
+    #[inline]
+    fn format() -> Self {
+        Self {
+            index: None,
+            separator: false,
+        }
+    }
+
+    /// Index of the element whose formatting failed. If formatting a
+    /// separator failed this is the index of the element after it. [`None`]
+    /// if the error isn't tied to an element or a separator.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Whether formatting the separator in front of the element failed.
+    #[inline]
+    pub fn is_separator(&self) -> bool {
+        self.separator
+    }
+}
+
+impl std::fmt::Display for JoinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(index) if self.separator => {
+                write!(f, "formatting the separator before element {} failed", index)
+            }
+            Some(index) => write!(f, "formatting element {} failed", index),
+            None => f.write_str("formatting failed"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// What [`Joiner::into_string()`] does if formatting an element or a separator
+/// fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Return the partial output.
+    Ignore,
+
+    /// Panic in debug builds, return the partial output in release builds.
+    #[default]
+    DebugAssert,
+
+    /// Always panic, like [`ToString::to_string()`] does.
+    Panic,
+}
+
+impl ErrorPolicy {
+    fn handle(self, error: JoinError) {
+        match self {
+            ErrorPolicy::Ignore => {}
+            ErrorPolicy::DebugAssert => {
+                if cfg!(debug_assertions) {
+                    panic!("a Display implementation returned an error unexpectedly: {}", error);
+                }
+            }
+            ErrorPolicy::Panic => {
+                panic!("a Display implementation returned an error unexpectedly: {}", error);
+            }
+        }
+    }
+}
+
+// =============================================================================
+//      trait Separator
+// =============================================================================
+
+/// Something that is written between the elements of a [`Joiner`].
+///
+/// This is implemented for everything that implements [`std::fmt::Display`],
+/// which writes the same separator everywhere. Implement it in order to write
+/// separators that depend on their position or on the elements around them.
+/// See also [`Cycle`], [`EveryNth`] and [`SepFn`].
+pub trait Separator<T> {
+    /// Write the separator with the given `index` into `f`.
+    ///
+    /// Separators are indexed as if no elements were elided, meaning the
+    /// separator in front of the element at index `i` has the index `i - 1`,
+    /// or `i` for [`SepMode::Leading`]. `prev` and `next` are the elements
+    /// around the separator, or [`None`] if the
+    /// [elision marker](Joiner::elision_marker()) is written in their place
+    /// or if there is no element there because of the [`SepMode`]. The flags
+    /// of `f` are always the defaults.
+    fn fmt_sep(
+        &self,
+        index: usize,
+        prev: Option<&T>,
+        next: Option<&T>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result;
+}
+
+impl<T, D> Separator<T> for D
+where
+    D: std::fmt::Display + ?Sized,
+{
+    #[inline]
+    fn fmt_sep(
+        &self,
+        _index: usize,
+        _prev: Option<&T>,
+        _next: Option<&T>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.fmt(f)
+    }
+}
+
+// =============================================================================
+//      traits DisplayElement and DebugElement
+// =============================================================================
+
+/// How a [`Joiner`] writes its elements for [`std::fmt::Display`] and the
+/// methods producing a [`String`].
+///
+/// This is implemented for [`DefaultFormat`], for closures taking a reference
+/// to the element and the [`std::fmt::Formatter`], as used by
+/// [`Joiner::format_with()`], and for the element formatters of the
+/// `join_*()` methods.
+pub trait DisplayElement<T> {
+    /// Write `element` into `f`.
+    fn fmt_display(&self, element: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+}
+
+/// How a [`Joiner`] writes its elements for [`std::fmt::Debug`]. See
+/// [`DisplayElement`].
+pub trait DebugElement<T> {
+    /// Write `element` into `f`.
+    fn fmt_debug(&self, element: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+}
+
+/// Writes every element using its own formatting trait implementation. This
+/// is what a [`Joiner`] does unless [`Joiner::format_with()`] is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DefaultFormat;
+
+impl<T> DisplayElement<T> for DefaultFormat
+where
+    T: std::fmt::Display,
+{
+    #[inline]
+    fn fmt_display(&self, element: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        element.fmt(f)
+    }
+}
+
+impl<T> DebugElement<T> for DefaultFormat
+where
+    T: std::fmt::Debug,
+{
+    #[inline]
+    fn fmt_debug(&self, element: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        element.fmt(f)
+    }
+}
+
+impl<T, F> DisplayElement<T> for F
+where
+    F: Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn fmt_display(&self, element: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self(element, f)
+    }
+}
+
+/// Uses the closure for [`std::fmt::Debug`] as well.
+impl<T, F> DebugElement<T> for F
+where
+    F: Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn fmt_debug(&self, element: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self(element, f)
+    }
+}
+
+/// Where separators are written. See [`Joiner::sep_mode()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SepMode {
+    /// Between the elements.
+    #[default]
+    Between,
+
+    /// In front of every element.
+    Leading,
+
+    /// After every element.
+    Terminator,
+}
+
+// =============================================================================
+//      struct Cycle
+// =============================================================================
+
+/// [`Separator`] that cycles through a list of separators.
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{Cycle, Join};
+///
+/// assert_eq!(
+///     (1..=6).join(Cycle::new([" + ", " - "])).into_string(),
+///     "1 + 2 - 3 + 4 - 5 + 6"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    seps: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Create a [`Cycle`] object. If `seps` is empty nothing is written
+    /// between the elements.
+    #[inline]
+    pub fn new(seps: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            seps: seps.into_iter().collect(),
+        }
+    }
+}
+
+impl<T, S> Separator<T> for Cycle<S>
+where
+    S: Separator<T>,
+{
+    #[inline]
+    fn fmt_sep(
+        &self,
+        index: usize,
+        prev: Option<&T>,
+        next: Option<&T>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        if self.seps.is_empty() {
+            return Ok(());
+        }
+        self.seps[index % self.seps.len()].fmt_sep(index, prev, next, f)
+    }
+}
+
+// =============================================================================
+//      struct EveryNth
+// =============================================================================
+
+/// [`Separator`] that writes a different separator after every `n`-th
+/// element, e.g. in order to break lines.
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{EveryNth, Join};
+///
+/// assert_eq!(
+///     (1..=7).join(EveryNth::new(3, ", ", ",\n")).into_string(),
+///     "1, 2, 3,\n4, 5, 6,\n7"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct EveryNth<S, N> {
+    n: usize,
+    sep: S,
+    nth: N,
+}
+
+impl<S, N> EveryNth<S, N> {
+    /// Create an [`EveryNth`] object that writes `nth` after every `n`-th
+    /// element and `sep` otherwise. If `n` is `0` only `sep` is written.
+    #[inline]
+    pub fn new(n: usize, sep: S, nth: N) -> Self {
+        Self { n, sep, nth }
+    }
+}
+
+impl<T, S, N> Separator<T> for EveryNth<S, N>
+where
+    S: Separator<T>,
+    N: Separator<T>,
+{
+    // usize::is_multiple_of() needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    #[inline]
+    fn fmt_sep(
+        &self,
+        index: usize,
+        prev: Option<&T>,
+        next: Option<&T>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        if self.n != 0 && (index + 1) % self.n == 0 {
+            self.nth.fmt_sep(index, prev, next, f)
+        } else {
+            self.sep.fmt_sep(index, prev, next, f)
+        }
+    }
+}
+
+// =============================================================================
+//      struct SepFn
+// =============================================================================
+
+/// [`Separator`] that is written by a closure.
+///
+/// The closure gets the same arguments as [`Separator::fmt_sep()`].
+///
+/// # Examples
+///
+/// ```
+/// use join_string::{Join, SepFn};
+///
+/// let words = ["Hello", ",", "world", "!"];
+/// let sep = SepFn::new(|_, _, next: Option<&&str>, f| match next {
+///     Some(&("," | "!")) => Ok(()),
+///     _ => f.write_str(" "),
+/// });
+/// assert_eq!(words.join(sep).into_string(), "Hello, world!");
+/// ```
+#[derive(Clone)]
+pub struct SepFn<F>(F);
+
+impl<F> SepFn<F> {
+    /// Create a [`SepFn`] object.
+    #[inline]
+    pub fn new<T>(func: F) -> Self
+    where
+        F: Fn(usize, Option<&T>, Option<&T>, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    {
+        Self(func)
+    }
+}
+
+impl<T, F> Separator<T> for SepFn<F>
+where
+    F: Fn(usize, Option<&T>, Option<&T>, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+{
+    #[inline]
+    fn fmt_sep(
+        &self,
+        index: usize,
+        prev: Option<&T>,
+        next: Option<&T>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        (self.0)(index, prev, next, f)
+    }
+}
+
+// =============================================================================
+//      trait Join
+// =============================================================================
+
+/// Trait that provides a method to join elements of an iterator, interspersing
+/// a separator between all elements.
+///
+/// This trait is implemented for anything that implements
+/// [`std::iter::IntoIterator`], which is e.g. arrays, slices, [`Vec`], and more.
+pub trait Join<I: std::iter::Iterator>: std::iter::IntoIterator<IntoIter = I> {
+    /// Join the elements of an iterator, interspersing a separator between
+    /// all elements.
+    ///
+    /// The elements need to implement [`std::fmt::Display`] and the separator
+    /// needs to implement [`Separator`], which every [`std::fmt::Display`] type
+    /// does.
+    #[inline]
+    fn join<S>(self, sep: S) -> Joiner<I, S>
+    where
+        Self: Sized,
+        S: Separator<I::Item>,
+    {
+        Joiner::new(self.into_iter(), sep)
+    }
+
+    /// Join the elements of a collection, interspersing a separator between
+    /// all elements, by borrowing the collection and iterating over it again
+    /// every time the result is formatted. The collection isn't consumed.
+    ///
+    /// The elements yielded when iterating over a reference to the collection
+    /// need to implement [`std::fmt::Display`] and the separator needs to
+    /// implement [`Separator`]. Use [`JoinerRef::new()`] for collections that
+    /// only implement [`std::iter::IntoIterator`] for references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let mut map = std::collections::BTreeMap::new();
+    /// map.insert(1, "foo");
+    /// map.insert(2, "bar");
+    ///
+    /// let joiner = map.join_by_ref(", ");
+    /// assert_eq!(format!("{:?}", joiner), "(1, \"foo\"), (2, \"bar\")");
+    /// assert_eq!(format!("{:?}", joiner), "(1, \"foo\"), (2, \"bar\")");
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    #[inline]
+    fn join_by_ref<'a, S>(&'a self, sep: S) -> JoinerRef<'a, Self, S>
+    where
+        Self: Sized,
+        &'a Self: std::iter::IntoIterator,
+        S: Separator<<&'a Self as std::iter::IntoIterator>::Item>,
+    {
+        JoinerRef::new(self, sep)
+    }
+
+    /// Join the elements of an iterator, interspersing a separator between
+    /// all elements, writing every element using `fmt`.
+    ///
+    /// `fmt` writes the element directly into the output, so no temporary
+    /// [`String`] is needed in order to format the elements differently than
+    /// their [`std::fmt::Display`] implementation does. This is the same as
+    /// `self.join(sep).format_with(fmt)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// assert_eq!(
+    ///     ["foo", "bar"].join_with(", ", |s, f| write!(f, "<{s}>")).into_string(),
+    ///     "<foo>, <bar>"
+    /// );
+    /// assert_eq!(
+    ///     [(1, 'a'), (2, 'b')].join_with(' ', |(n, c), f| write!(f, "{n}={c}")).into_string(),
+    ///     "1=a 2=b"
+    /// );
+    /// ```
+    #[inline]
+    fn join_with<S, F>(self, sep: S, fmt: F) -> Joiner<I, S, F>
+    where
+        Self: Sized,
+        S: Separator<I::Item>,
+        F: Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    {
+        Joiner::new(self.into_iter(), sep).format_with(fmt)
+    }
+
+    /// Join the elements of an iterator, interspersing a separator between
+    /// all elements and writing a number in front of every element.
+    ///
+    /// This is the same as `self.join(sep).numbered(numbering)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::{Join, NumberStyle, Numbering};
+    ///
+    /// let items = ["foo", "bar", "baz", "qux"];
+    /// assert_eq!(
+    ///     items.join_numbered('\n', Numbering::new(NumberStyle::UpperRoman).start(2)).into_string(),
+    ///     " II. foo\nIII. bar\n IV. baz\n  V. qux"
+    /// );
+    /// ```
+    #[inline]
+    fn join_numbered<S>(self, sep: S, numbering: Numbering) -> Joiner<I, S>
+    where
+        Self: Sized,
+        S: Separator<I::Item>,
+    {
+        self.join(sep).numbered(numbering)
+    }
+
+    /// Join the elements of an iterator in groups, interspersing `sep`
+    /// between the elements of a group and the group separator of `chunks`
+    /// between the groups.
+    ///
+    /// This is the same as `self.join(sep).chunked(chunks)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::{Chunks, Join};
+    ///
+    /// let bytes = b"Hello, world!";
+    /// assert_eq!(
+    ///     format!("{:02x}", bytes.join_chunked(' ', Chunks::new(8, "\n"))),
+    ///     "48 65 6c 6c 6f 2c 20 77\n6f 72 6c 64 21"
+    /// );
+    /// ```
+    #[inline]
+    fn join_chunked<S>(self, sep: S, chunks: Chunks) -> Joiner<I, S>
+    where
+        Self: Sized,
+        S: Separator<I::Item>,
+    {
+        self.join(sep).chunked(chunks)
+    }
+
+    /// Join the elements of an iterator in sorted order, interspersing a
+    /// separator between all elements.
+    ///
+    /// This makes the output deterministic for collections with an unspecified
+    /// iteration order, like [`std::collections::HashSet`]. The elements are
+    /// collected into a [`Vec`] and sorted using [`slice::sort_unstable()`].
+    /// Nothing is cloned, so when joining references only the references are
+    /// sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let set: std::collections::HashSet<_> = ["foo", "bar", "baz"].into_iter().collect();
+    /// assert_eq!(set.iter().join_sorted(", ").to_string(), "bar, baz, foo");
+    /// ```
+    #[inline]
+    fn join_sorted<S>(self, sep: S) -> Joiner<std::vec::IntoIter<I::Item>, S>
+    where
+        Self: Sized,
+        I::Item: Ord,
+        S: Separator<I::Item>,
+    {
+        let mut items: Vec<_> = self.into_iter().collect();
+        items.sort_unstable();
+        Joiner::new(items.into_iter(), sep)
+    }
+
+    /// Join the elements of an iterator sorted by the key `key` extracts from
+    /// them, interspersing a separator between all elements.
+    ///
+    /// Elements with equal keys keep the order of the iterator. See
+    /// [`Join::join_sorted()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let words = ["bb", "a", "cc", "b"];
+    /// assert_eq!(words.join_sorted_by_key(' ', |word| word.len()).into_string(), "a b bb cc");
+    /// ```
+    #[inline]
+    fn join_sorted_by_key<S, K, F>(self, sep: S, key: F) -> Joiner<std::vec::IntoIter<I::Item>, S>
+    where
+        Self: Sized,
+        F: FnMut(&I::Item) -> K,
+        K: Ord,
+        S: Separator<I::Item>,
+    {
+        let mut items: Vec<_> = self.into_iter().collect();
+        items.sort_by_key(key);
+        Joiner::new(items.into_iter(), sep)
+    }
+
+    /// Join the distinct elements of an iterator in sorted order, interspersing
+    /// a separator between all elements.
+    ///
+    /// Of elements that compare equal only one is written. See
+    /// [`Join::join_sorted()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// assert_eq!([3, 1, 2, 3, 1].join_unique(", ").into_string(), "1, 2, 3");
+    /// ```
+    #[inline]
+    fn join_unique<S>(self, sep: S) -> Joiner<std::vec::IntoIter<I::Item>, S>
+    where
+        Self: Sized,
+        I::Item: Ord,
+        S: Separator<I::Item>,
+    {
+        let mut items: Vec<_> = self.into_iter().collect();
+        items.sort_unstable();
+        items.dedup();
+        Joiner::new(items.into_iter(), sep)
+    }
+
+    /// Join the elements of an iterator in groups of consecutive elements with
+    /// the same key.
+    ///
+    /// Every group is written as its key, `key_sep` and the elements of the
+    /// group interspersed with `inner_sep`. The groups are interspersed with
+    /// `group_sep`. The key is the result of calling `key` with the element.
+    ///
+    /// This is done in a single pass over the iterator, writing the elements
+    /// of every group as they are read. Use [`Join::join_grouped_sorted()`] if
+    /// elements with the same key aren't already next to each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// assert_eq!(
+    ///     [1, 2, 13, 21, 25].join_grouped(|n| n / 10, ": ", ", ", "; ").into_string(),
+    ///     "0: 1, 2; 1: 13; 2: 21, 25"
+    /// );
+    ///
+    /// let files = ["src/lib.rs", "src/main.rs", "tests/tests.rs"];
+    /// assert_eq!(
+    ///     files
+    ///         .join_grouped(|path| path.split('/').next().unwrap(), ": ", " ", "\n")
+    ///         .into_string(),
+    ///     "src: src/lib.rs src/main.rs\ntests: tests/tests.rs"
+    /// );
+    /// ```
+    #[inline]
+    fn join_grouped<K, F, KS, IS, GS>(
+        self,
+        key: F,
+        key_sep: KS,
+        inner_sep: IS,
+        group_sep: GS,
+    ) -> Joiner<GroupedIter<I, K, F>, GS, GroupFormat<KS, IS>>
+    where
+        Self: Sized,
+        F: FnMut(&I::Item) -> K,
+        K: PartialEq,
+        KS: std::fmt::Display,
+        IS: Separator<I::Item>,
+        GS: Separator<Group<K, I, F>>,
+    {
+        Joiner::new(GroupedIter::new(self, key), group_sep)
+            .with_format(GroupFormat::new(key_sep, inner_sep))
+    }
+
+    /// Like [`Join::join_grouped()`], but all elements with the same key are
+    /// grouped together and the groups are sorted by their key.
+    ///
+    /// The elements are collected into a [`Vec`] and sorted by their key
+    /// before grouping them. Elements with the same key keep the order of the
+    /// iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let words = ["banana", "avocado", "blueberry", "apple", "cherry"];
+    /// assert_eq!(
+    ///     words.join_grouped_sorted(|word| word.chars().next().unwrap(), ": ", ", ", "\n").into_string(),
+    ///     "a: avocado, apple\nb: banana, blueberry\nc: cherry"
+    /// );
+    /// ```
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn join_grouped_sorted<K, F, KS, IS, GS>(
+        self,
+        mut key: F,
+        key_sep: KS,
+        inner_sep: IS,
+        group_sep: GS,
+    ) -> Joiner<GroupedIter<std::vec::IntoIter<I::Item>, K, F>, GS, GroupFormat<KS, IS>>
+    where
+        Self: Sized,
+        F: FnMut(&I::Item) -> K,
+        K: Ord,
+        KS: std::fmt::Display,
+        IS: Separator<I::Item>,
+        GS: Separator<Group<K, std::vec::IntoIter<I::Item>, F>>,
+    {
+        let mut items: Vec<_> = self.into_iter().collect();
+        items.sort_by_key(&mut key);
+        Joiner::new(GroupedIter::new(items, key), group_sep)
+            .with_format(GroupFormat::new(key_sep, inner_sep))
+    }
+
+    /// Join integers, collapsing runs of consecutive integers into ranges.
+    ///
+    /// See [`Ranges`] for the range marker, the minimum length of a run and
+    /// the handling of unsorted input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::{Join, Ranges};
+    ///
+    /// let pages: Vec<u32> = vec![1, 2, 3, 5, 7, 8, 9];
+    /// assert_eq!(pages.join_ranges(", ", Ranges::default()).into_string(), "1-3, 5, 7-9");
+    ///
+    /// let cpus = [0u8, 1, 2, 3, 6];
+    /// assert_eq!(cpus.iter().copied().join_ranges(',', Ranges::default()).wrap("cpus ", "").into_string(), "cpus 0-3,6");
+    /// ```
+    #[inline]
+    fn join_ranges<S>(self, sep: S, ranges: Ranges) -> Joiner<RangesIter<I>, S, RunFormat>
+    where
+        Self: Sized,
+        I::Item: Step,
+        S: Separator<Run<I::Item>>,
+    {
+        let Ranges { marker, min_run, sort } = ranges;
+        Joiner::new(RangesIter::new(self, min_run, sort), sep)
+            .with_format(RunFormat::new(marker))
+    }
+
+    /// Join the key-value pairs of an iterator, writing `kv_sep` between the
+    /// key and the value of every pair and interspersing `pair_sep` between
+    /// the pairs.
+    ///
+    /// The elements need to be 2-tuples or references to them (see [`Pair`]),
+    /// which is what maps and iterators over [`Vec`]s of tuples yield. The key
+    /// and the value are written directly into the output, so no temporary
+    /// [`String`] is created per pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let mut map = std::collections::BTreeMap::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// assert_eq!(map.iter().join_pairs('=', '&').into_string(), "a=1&b=2");
+    ///
+    /// let pairs = vec![("x", 1.5), ("y", 2.25)];
+    /// assert_eq!(format!("{:.1}", pairs.iter().join_pairs(": ", ", ")), "x: 1.5, y: 2.2");
+    /// ```
+    #[inline]
+    fn join_pairs<KS, PS>(self, kv_sep: KS, pair_sep: PS) -> Joiner<I, PS, PairFormat<KS>>
+    where
+        Self: Sized,
+        I::Item: Pair,
+        KS: std::fmt::Display,
+        PS: Separator<I::Item>,
+    {
+        Joiner::new(self.into_iter(), pair_sep).with_format(PairFormat::new(kv_sep))
+    }
+
+    /// Join the elements of an iterator over iterables, interspersing `sep`
+    /// between the outer elements and `inner_sep` between the elements of
+    /// every inner iterable.
+    ///
+    /// The inner iterators are consumed while writing, so they don't need to
+    /// implement [`Clone`]. Only the outer iterator needs to implement
+    /// [`Clone`] in order to use [`std::fmt::Display`], just like for
+    /// [`Join::join()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let rows = vec![vec![1, 2], vec![3, 4], vec![5]];
+    /// assert_eq!(rows.iter().join_nested(';', ',').into_string(), "1,2;3,4;5");
+    ///
+    /// let lines = "a b\nc d e";
+    /// assert_eq!(
+    ///     lines.lines().map(str::split_whitespace).join_nested(" / ", '+').to_string(),
+    ///     "a+b / c+d+e"
+    /// );
+    /// ```
+    #[inline]
+    fn join_nested<S, N>(self, sep: S, inner_sep: N) -> Joiner<NestedIter<I>, S, NestedFormat<N>>
+    where
+        Self: Sized,
+        I::Item: std::iter::IntoIterator,
+        N: Separator<<I::Item as std::iter::IntoIterator>::Item>,
+        S: Separator<Nested<<I::Item as std::iter::IntoIterator>::IntoIter>>,
+    {
+        Joiner::new(NestedIter::new(self), sep).with_format(NestedFormat::new(inner_sep))
+    }
+
+    /// Like [`Join::join_nested()`], but for three levels of nesting, with one
+    /// separator per level starting at the outermost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use join_string::Join;
+    ///
+    /// let cube = [[[1, 2], [3, 4]], [[5, 6], [7, 8]]];
+    /// assert_eq!(cube.join_nested3(" | ", "; ", ',').into_string(), "1,2; 3,4 | 5,6; 7,8");
+    /// ```
+    #[inline]
+    fn join_nested3<S1, S2, S3>(
+        self,
+        sep: S1,
+        inner_sep: S2,
+        innermost_sep: S3,
+    ) -> Joiner<NestedIter3<I>, S1, NestedFormat<S2, NestedFormat<S3>>>
+    where
+        Self: Sized,
+        I::Item: std::iter::IntoIterator,
+        <I::Item as std::iter::IntoIterator>::Item: std::iter::IntoIterator,
+        S3: Separator<<<I::Item as std::iter::IntoIterator>::Item as std::iter::IntoIterator>::Item>,
+        S2: Separator<Nested<<<I::Item as std::iter::IntoIterator>::Item as std::iter::IntoIterator>::IntoIter>>,
+        S1: Separator<Nested<NestedIter<<I::Item as std::iter::IntoIterator>::IntoIter>>>,
+    {
+        let fmt = NestedFormat::new(inner_sep).format(NestedFormat::new(innermost_sep));
+        Joiner::new(NestedIter3::new(self), sep).with_format(fmt)
+    }
+
+    /// Join the [`Ok`] values of an iterator over [`Result`]s, interspersing a
+    /// separator between all elements.
+    ///
+    /// Writing stops at the first [`Err`], which is then returned by the
+    /// output methods of the returned [`TryJoiner`]. The [`Ok`] values need to
+    /// implement [`std::fmt::Display`] and the separator needs to implement
+    /// [`Separator`].
+    #[inline]
+    fn try_join<S, T, E>(self, sep: S) -> TryJoiner<I, S>
+    where
+        Self: Sized,
+        I: std::iter::Iterator<Item = Result<T, E>>,
+        S: Separator<T>,
+    {
+        TryJoiner::new(self.into_iter(), sep)
+    }
+
+    /// Join the elements of an iterator, interspersing a separator between
+    /// all elements.
+    ///
+    /// The elements and the separator need to implement [`AsRef<str>`].
+    #[inline]
+    fn join_str<S>(self, sep: S) -> Joiner<DisplayIter<I>, DisplayWrapper<S>>
+    where
+        Self: Sized,
+        S: AsRef<str>,
+        I::Item: AsRef<str>,
+    {
+        Joiner::new(
+            DisplayIter {
+                iter: self.into_iter(),
+            },
+            DisplayWrapper(sep),
+        )
+    }
+}
+
+impl<T> Join<T::IntoIter> for T where T: std::iter::IntoIterator {}
+
+// =============================================================================
+//      struct DisplayWrapper
+// =============================================================================
+
+/// Helper for joining elements that only implement [`AsRef<str>`], but not [`std::fmt::Display`].
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct DisplayWrapper<T: AsRef<str>>(T);
+
+impl<T: AsRef<str>> DisplayWrapper<T> {
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> std::fmt::Display for DisplayWrapper<T>
+where
+    T: AsRef<str>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.as_ref().fmt(f)
+    }
+}
+
+impl<T> Clone for DisplayWrapper<T>
+where
+    T: AsRef<str>,
+    T: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> AsRef<str> for DisplayWrapper<T>
+where
+    T: AsRef<str>,
+{
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+// =============================================================================
+//      struct DisplayIter
+// =============================================================================
+
+/// Iterator-facade that maps an iterator over [`AsRef<str>`] to an iterator
+/// over [`DisplayWrapper`].
+///
+/// This is used to implement [`Join::join_str()`].
+#[derive(Debug)]
+pub struct DisplayIter<I>
+where
+    I: std::iter::Iterator,
+{
+    iter: I,
+}
+
+impl<I> DisplayIter<I>
+where
+    I: std::iter::Iterator,
+{
+    #[inline]
+    pub fn new(elements: impl Join<I>) -> Self {
+        Self {
+            iter: elements.into_iter(),
+        }
+    }
+}
+
+impl<I> std::iter::Iterator for DisplayIter<I>
+where
+    I: std::iter::Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = DisplayWrapper<I::Item>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.iter.next() {
+            return Some(DisplayWrapper(item));
+        }
+        None
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        if let Some(item) = self.iter.last() {
+            return Some(DisplayWrapper(item));
+        }
+        None
+    }
+
+    #[inline]
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(DisplayWrapper)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    #[cfg(target_feature = "iter_advance_by")]
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+        self.iter.advance_by(n)
+    }
+
+    #[inline]
+    #[cfg(target_feature = "trusted_random_access")]
+    unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
+    where
+        Self: TrustedRandomAccessNoCoerce,
+    {
+        DisplayWrapper(self.iter.__iterator_get_unchecked(idx))
+    }
+}
+
+impl<I> std::iter::ExactSizeIterator for DisplayIter<I>
+where
+    I: std::iter::ExactSizeIterator,
+    I::Item: AsRef<str>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    #[inline]
+    #[cfg(target_feature = "exact_size_is_empty")]
+    fn is_empty(&self) -> bool {
+        self.iter.is_empty()
+    }
+}
+
+impl<I> std::iter::DoubleEndedIterator for DisplayIter<I>
+where
+    I: std::iter::DoubleEndedIterator,
+    I::Item: AsRef<str>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.iter.next_back() {
+            return Some(DisplayWrapper(item));
+        }
+        None
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if let Some(item) = self.iter.nth_back(n) {
+            return Some(DisplayWrapper(item));
+        }
+        None
+    }
+
+    #[inline]
+    #[cfg(target_feature = "iter_advance_by")]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+        self.iter.advance_back_by(n)
+    }
+}
+
+impl<I> Clone for DisplayIter<I>
+where
+    I: std::iter::Iterator,
+    I: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+// =============================================================================
+//      trait Pair
+// =============================================================================
+
+/// A key-value pair that can be joined using [`Join::join_pairs()`].
+///
+/// This is implemented for 2-tuples and references to them.
+pub trait Pair {
+    type Key;
+    type Value;
+
+    /// The first element of the pair.
+    fn key(&self) -> &Self::Key;
+
+    /// The second element of the pair.
+    fn value(&self) -> &Self::Value;
+}
+
+impl<K, V> Pair for (K, V) {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn key(&self) -> &K {
+        &self.0
+    }
+
+    #[inline]
+    fn value(&self) -> &V {
+        &self.1
+    }
+}
+
+impl<K, V> Pair for &(K, V) {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn key(&self) -> &K {
+        &self.0
+    }
+
+    #[inline]
+    fn value(&self) -> &V {
+        &self.1
+    }
+}
+
+// =============================================================================
+//      struct PairFormat
+// =============================================================================
+
+/// Element formatter of [`Join::join_pairs()`] that writes every [`Pair`] as
+/// the key, the separator and the value.
+///
+/// The flags of the [`std::fmt::Formatter`] are applied to the key and the
+/// value, the separator is written without them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PairFormat<S>
+where
+    S: std::fmt::Display,
+{
+    sep: S,
+}
+
+impl<S> PairFormat<S>
+where
+    S: std::fmt::Display,
+{
+    /// Create a [`PairFormat`] object.
+    #[inline]
+    pub fn new(sep: S) -> Self {
+        Self { sep }
+    }
+
+    /// The separator written between the key and the value.
+    #[inline]
+    pub fn sep(&self) -> &S {
+        &self.sep
+    }
+}
+
+impl<P, S> DisplayElement<P> for PairFormat<S>
+where
+    P: Pair,
+    P::Key: std::fmt::Display,
+    P::Value: std::fmt::Display,
+    S: std::fmt::Display,
+{
+    #[inline]
+    fn fmt_display(&self, pair: &P, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(pair.key(), f)?;
+        f.write_fmt(format_args!("{}", self.sep))?;
+        std::fmt::Display::fmt(pair.value(), f)
+    }
+}
+
+impl<P, S> DebugElement<P> for PairFormat<S>
+where
+    P: Pair,
+    P::Key: std::fmt::Debug,
+    P::Value: std::fmt::Debug,
+    S: std::fmt::Display,
+{
+    #[inline]
+    fn fmt_debug(&self, pair: &P, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(pair.key(), f)?;
+        f.write_fmt(format_args!("{}", self.sep))?;
+        std::fmt::Debug::fmt(pair.value(), f)
+    }
+}
+
+// =============================================================================
+//      struct Group
+// =============================================================================
+
+/// A group of consecutive elements with the same key, yielded by
+/// [`GroupedIter`].
+///
+/// The elements aren't collected. They are read from the iterator that is
+/// shared with the [`GroupedIter`] while the group is iterated or written by
+/// the [`GroupFormat`] of the [`Joiner`], so a group can only be read once.
+/// If the [`GroupedIter`] is advanced before a group was read, the remaining
+/// elements of the group are buffered until it is read or dropped.
+pub struct Group<K, I, F>
+where
+    I: std::iter::Iterator,
+{
+    key: std::rc::Rc<K>,
+    index: usize,
+    first: std::cell::Cell<Option<I::Item>>,
+    source: std::rc::Rc<std::cell::RefCell<GroupSource<I, K, F>>>,
+}
+
+impl<K, I, F> Group<K, I, F>
+where
+    I: std::iter::Iterator,
+{
+    /// The key of the group.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K, I, F> Group<K, I, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    fn next_item(&self) -> Option<I::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        let mut source = self.source.borrow_mut();
+        if let Some(&(index, _)) = source.buffered.front() {
+            if index == self.index {
+                return source.buffered.pop_front().map(|(_, item)| item);
+            }
+        }
+        if source.index == self.index {
+            source.next_current()
+        } else {
+            None
+        }
+    }
+}
+
+impl<K, I, F> std::iter::Iterator for Group<K, I, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_item()
+    }
+}
+
+impl<K, I, F> Drop for Group<K, I, F>
+where
+    I: std::iter::Iterator,
+{
+    fn drop(&mut self) {
+        // The elements of a dropped group are never read, so they don't need
+        // to be buffered anymore.
+        if let Ok(mut source) = self.source.try_borrow_mut() {
+            if source.index == self.index {
+                source.current_alive = false;
+            }
+            source.buffered.retain(|(index, _)| *index != self.index);
+        }
+    }
+}
+
+/// The iterator of a [`GroupedIter`], shared with the [`Group`]s it yields.
+struct GroupSource<I, K, F>
+where
+    I: std::iter::Iterator,
+{
+    iter: I,
+    key: F,
+    /// The key of the group `iter` is in, shared with that [`Group`], and its
+    /// index.
+    current: Option<std::rc::Rc<K>>,
+    index: usize,
+    /// Whether the [`Group`] `iter` is in wasn't dropped yet.
+    current_alive: bool,
+    /// The first element of the next group, read while looking for the end
+    /// of the current group.
+    pending: Option<(K, I::Item)>,
+    /// The remaining elements of earlier groups, read before those groups
+    /// were, with the index of their group.
+    buffered: std::collections::VecDeque<(usize, I::Item)>,
+}
+
+impl<I, K, F> GroupSource<I, K, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    /// Reads the next element of the current group, if there is one.
+    fn next_current(&mut self) -> Option<I::Item> {
+        let current = self.current.as_ref()?;
+        if self.pending.is_some() {
+            return None;
+        }
+        let item = self.iter.next()?;
+        let key = (self.key)(&item);
+        if key == **current {
+            return Some(item);
+        }
+        self.pending = Some((key, item));
+        None
+    }
+}
+
+// =============================================================================
+//      struct GroupFormat
+// =============================================================================
+
+/// Element formatter of [`Join::join_grouped()`] that writes every [`Group`]
+/// as its key, the key separator and its elements interspersed with the inner
+/// separator.
+///
+/// The flags of the [`std::fmt::Formatter`] are applied to the key and the
+/// elements, the separators are written without them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GroupFormat<KS, IS> {
+    key_sep: KS,
+    inner_sep: IS,
+}
+
+impl<KS, IS> GroupFormat<KS, IS> {
+    /// Create a [`GroupFormat`] object.
+    #[inline]
+    pub fn new(key_sep: KS, inner_sep: IS) -> Self {
+        Self { key_sep, inner_sep }
+    }
+
+    /// The separator written between the key and the elements.
+    #[inline]
+    pub fn key_sep(&self) -> &KS {
+        &self.key_sep
+    }
+
+    /// The separator written between the elements.
+    #[inline]
+    pub fn inner_sep(&self) -> &IS {
+        &self.inner_sep
+    }
+
+    fn fmt_group<K, I, F>(
+        &self,
+        group: &Group<K, I, F>,
+        f: &mut std::fmt::Formatter<'_>,
+        fmt_key: impl Fn(&K, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+        fmt_item: impl Fn(&I::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    ) -> std::fmt::Result
+    where
+        I: std::iter::Iterator,
+        F: FnMut(&I::Item) -> K,
+        K: PartialEq,
+        KS: std::fmt::Display,
+        IS: Separator<I::Item>,
+    {
+        fmt_key(group.key(), f)?;
+        f.write_fmt(format_args!("{}", self.key_sep))?;
+        let Some(mut prev) = group.next_item() else {
+            return Ok(());
+        };
+        fmt_item(&prev, f)?;
+        let mut index = 0;
+        while let Some(item) = group.next_item() {
+            f.write_fmt(format_args!(
+                "{}",
+                FmtFn(|f: &mut std::fmt::Formatter<'_>| {
+                    self.inner_sep.fmt_sep(index, Some(&prev), Some(&item), f)
+                })
+            ))?;
+            fmt_item(&item, f)?;
+            prev = item;
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<K, I, F, KS, IS> DisplayElement<Group<K, I, F>> for GroupFormat<KS, IS>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+    K: std::fmt::Display,
+    I::Item: std::fmt::Display,
+    KS: std::fmt::Display,
+    IS: Separator<I::Item>,
+{
+    #[inline]
+    fn fmt_display(
+        &self,
+        group: &Group<K, I, F>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.fmt_group(group, f, std::fmt::Display::fmt, std::fmt::Display::fmt)
+    }
+}
+
+impl<K, I, F, KS, IS> DebugElement<Group<K, I, F>> for GroupFormat<KS, IS>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+    K: std::fmt::Debug,
+    I::Item: std::fmt::Debug,
+    KS: std::fmt::Display,
+    IS: Separator<I::Item>,
+{
+    #[inline]
+    fn fmt_debug(
+        &self,
+        group: &Group<K, I, F>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        self.fmt_group(group, f, std::fmt::Debug::fmt, std::fmt::Debug::fmt)
+    }
+}
+
+// =============================================================================
+//      struct GroupedIter
+// =============================================================================
+
+/// Iterator-facade that groups consecutive elements of an iterator with the
+/// same key into [`Group`]s.
+///
+/// This is used to implement [`Join::join_grouped()`].
+pub struct GroupedIter<I, K, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    source: std::rc::Rc<std::cell::RefCell<GroupSource<I, K, F>>>,
+    groups: usize,
+}
+
+impl<I, K, F> GroupedIter<I, K, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    #[inline]
+    pub fn new(elements: impl Join<I>, key: F) -> Self {
+        Self {
+            source: std::rc::Rc::new(std::cell::RefCell::new(GroupSource {
+                iter: elements.into_iter(),
+                key,
+                current: None,
+                index: 0,
+                current_alive: false,
+                pending: None,
+                buffered: std::collections::VecDeque::new(),
+            })),
+            groups: 0,
+        }
+    }
+}
+
+impl<I, K, F> std::iter::Iterator for GroupedIter<I, K, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = Group<K, I, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut source = self.source.borrow_mut();
+        // Skip the rest of the current group, keeping it for later if the
+        // group is still to be read.
+        while let Some(item) = source.next_current() {
+            if source.current_alive {
+                let index = source.index;
+                source.buffered.push_back((index, item));
+            }
+        }
+
+        let (key, first) = match source.pending.take() {
+            Some(pending) => pending,
+            None if self.groups == 0 => {
+                let item = source.iter.next()?;
+                ((source.key)(&item), item)
+            }
+            None => return None,
+        };
+        let key = std::rc::Rc::new(key);
+        source.current = Some(std::rc::Rc::clone(&key));
+        source.index = self.groups;
+        source.current_alive = true;
+        drop(source);
+
+        let group = Group {
+            key,
+            index: self.groups,
+            first: std::cell::Cell::new(Some(first)),
+            source: std::rc::Rc::clone(&self.source),
+        };
+        self.groups += 1;
+        Some(group)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let source = self.source.borrow();
+        let pending = usize::from(source.pending.is_some());
+        let (lower, upper) = source.iter.size_hint();
+        (
+            usize::from(pending > 0 || (self.groups == 0 && lower > 0)),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+impl<I, K, F> Clone for GroupedIter<I, K, F>
+where
+    I: std::iter::Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+    I: Clone,
+    I::Item: Clone,
+    K: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        let source = self.source.borrow();
+        // The groups yielded so far read from the source of `self`, so the
+        // clone neither keeps their elements nor buffers them.
+        let source = GroupSource {
+            iter: source.iter.clone(),
+            key: source.key.clone(),
+            current: source.current.clone(),
+            index: source.index,
+            current_alive: false,
+            pending: source.pending.clone(),
+            buffered: std::collections::VecDeque::new(),
+        };
+        Self {
+            source: std::rc::Rc::new(std::cell::RefCell::new(source)),
+            groups: self.groups,
+        }
+    }
+}
+
+// =============================================================================
+//      struct Run
+// =============================================================================
+
+/// A run of consecutive integers, yielded by [`RangesIter`].
+///
+/// It is written by the [`RunFormat`] of the [`Joiner`]. This is used to
+/// implement [`Join::join_ranges()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run<T>
+where
+    T: Step,
+{
+    start: T,
+    end: T,
+}
+
+impl<T> Run<T>
+where
+    T: Step,
+{
+    /// The first integer of the run.
+    #[inline]
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// The last integer of the run.
+    #[inline]
+    pub fn end(&self) -> T {
+        self.end
+    }
+}
+
+// =============================================================================
+//      struct RunFormat
+// =============================================================================
+
+/// Element formatter of [`Join::join_ranges()`] that writes every [`Run`] as
+/// the first integer, the range marker and the last integer, or just as the
+/// integer if the run only contains one.
+///
+/// The flags of the [`std::fmt::Formatter`] are applied to the integers, the
+/// marker is written without them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunFormat {
+    marker: std::borrow::Cow<'static, str>,
+}
+
+impl Default for RunFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new("-")
+    }
+}
+
+impl RunFormat {
+    /// Create a [`RunFormat`] object with the given range marker.
+    #[inline]
+    pub fn new(marker: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self {
+            marker: marker.into(),
+        }
+    }
+
+    /// The range marker.
+    #[inline]
+    pub fn marker(&self) -> &str {
+        &self.marker
+    }
+
+    fn fmt_run<T>(
+        &self,
+        run: &Run<T>,
+        f: &mut std::fmt::Formatter<'_>,
+        fmt_item: impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    ) -> std::fmt::Result
+    where
+        T: Step,
+    {
+        fmt_item(&run.start, f)?;
+        if run.start != run.end {
+            f.write_str(&self.marker)?;
+            fmt_item(&run.end, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> DisplayElement<Run<T>> for RunFormat
+where
+    T: Step,
+    T: std::fmt::Display,
+{
+    #[inline]
+    fn fmt_display(&self, run: &Run<T>, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_run(run, f, std::fmt::Display::fmt)
+    }
+}
+
+impl<T> DebugElement<Run<T>> for RunFormat
+where
+    T: Step,
+    T: std::fmt::Debug,
+{
+    #[inline]
+    fn fmt_debug(&self, run: &Run<T>, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_run(run, f, std::fmt::Debug::fmt)
     }
 }
 
-impl<I, S> From<Joiner<I, S>> for String
+// =============================================================================
+//      struct RangesIter
+// =============================================================================
+
+/// Iterator-facade that collapses consecutive integers of an iterator into
+/// [`Run`]s.
+///
+/// This is used to implement [`Join::join_ranges()`].
+pub struct RangesIter<I>
+where
+    I: std::iter::Iterator,
+    I::Item: Step,
+{
+    source: RangesSource<I>,
+    min_run: usize,
+    /// The first integer of the next run, read while looking for the end of
+    /// the previous run.
+    pending: Option<I::Item>,
+    /// The remaining integers of a run that was too short to be collapsed.
+    singles: Option<(I::Item, I::Item)>,
+}
+
+/// The integers a [`RangesIter`] reads, either straight from the iterator or
+/// sorted up front.
+#[derive(Clone)]
+enum RangesSource<I>
+where
+    I: std::iter::Iterator,
+{
+    Iter(I),
+    Sorted(std::vec::IntoIter<I::Item>),
+}
+
+impl<I> std::iter::Iterator for RangesSource<I>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
-    I::Item: std::fmt::Display,
 {
+    type Item = I::Item;
+
     #[inline]
-    fn from(value: Joiner<I, S>) -> Self {
-        value.into_string()
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Iter(iter) => iter.next(),
+            Self::Sorted(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Iter(iter) => iter.size_hint(),
+            Self::Sorted(iter) => iter.size_hint(),
+        }
     }
 }
 
-impl<I, S> Clone for Joiner<I, S>
+impl<I> RangesIter<I>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
-    I::Item: std::fmt::Display,
-    I: Clone,
-    S: Clone,
+    I::Item: Step,
 {
+    /// Create a [`RangesIter`] object. `min_run` and `sort` are as described
+    /// for [`Ranges`].
     #[inline]
-    fn clone(&self) -> Self {
+    pub fn new(elements: impl Join<I>, min_run: usize, sort: bool) -> Self {
+        let source = if sort {
+            let mut items: Vec<_> = elements.into_iter().collect();
+            items.sort_unstable();
+            items.dedup();
+            RangesSource::Sorted(items.into_iter())
+        } else {
+            RangesSource::Iter(elements.into_iter())
+        };
+
         Self {
-            iter: self.iter.clone(),
-            sep: self.sep.clone(),
+            source,
+            min_run,
+            pending: None,
+            singles: None,
         }
     }
 }
 
-impl<I, S> std::fmt::Display for Joiner<I, S>
+impl<I> std::iter::Iterator for RangesIter<I>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
-    I::Item: std::fmt::Display,
-    I: Clone,
+    I::Item: Step,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut iter = self.iter.clone();
-        if let Some(first) = iter.next() {
-            first.fmt(f)?;
-            for item in iter {
-                self.sep.fmt(f)?;
-                item.fmt(f)?;
+    type Item = Run<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((value, end)) = self.singles.take() {
+            if value != end {
+                self.singles = value.successor().map(|next| (next, end));
             }
+            return Some(Run { start: value, end: value });
         }
-        Ok(())
+
+        let start = match self.pending.take() {
+            Some(start) => start,
+            None => self.source.next()?,
+        };
+
+        let mut end = start;
+        let mut len = 1usize;
+        for item in self.source.by_ref() {
+            if end.successor() != Some(item) {
+                self.pending = Some(item);
+                break;
+            }
+            end = item;
+            len = len.saturating_add(1);
+        }
+
+        if len >= self.min_run {
+            return Some(Run { start, end });
+        }
+
+        if start != end {
+            self.singles = start.successor().map(|next| (next, end));
+        }
+        Some(Run { start, end: start })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = usize::from(self.pending.is_some());
+        let singles = if self.singles.is_some() { self.min_run } else { 0 };
+        let (lower, upper) = self.source.size_hint();
+        (
+            usize::from(buffered + singles + lower > 0),
+            upper.and_then(|upper| upper.checked_add(buffered + singles)),
+        )
     }
 }
 
-impl<I, S> std::fmt::Debug for Joiner<I, S>
+impl<I> Clone for RangesIter<I>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
-    I::Item: std::fmt::Debug,
+    I::Item: Step,
     I: Clone,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut iter = self.iter.clone();
-        if let Some(first) = iter.next() {
-            first.fmt(f)?;
-            for item in iter {
-                self.sep.fmt(f)?;
-                item.fmt(f)?;
-            }
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            min_run: self.min_run,
+            pending: self.pending,
+            singles: self.singles,
         }
-        Ok(())
     }
 }
+
 // =============================================================================
-//      trait Join
+//      struct Nested
 // =============================================================================
 
-/// Trait that provides a method to join elements of an iterator, interspersing
-/// a separator between all elements.
+/// An inner iterator of [`Join::join_nested()`] and [`Join::join_nested3()`].
 ///
-/// This trait is implemented for anything that implements
-/// [`std::iter::IntoIterator`], which is e.g. arrays, slices, [`Vec`], and more.
-pub trait Join<I: std::iter::Iterator>: std::iter::IntoIterator<IntoIter = I> {
-    /// Join the elements of an iterator, interspersing a separator between
-    /// all elements.
-    ///
-    /// The elements and the separator need to implement [`std::fmt::Display`].
+/// Like a [`OnceJoiner`], it is consumed when it is written the first time
+/// and writes nothing after that. It is written by the [`NestedFormat`] of
+/// the [`Joiner`], which holds the inner separator.
+pub struct Nested<J>(std::cell::Cell<Option<J>>);
+
+impl<J> Nested<J> {
+    /// Create a [`Nested`] object.
     #[inline]
-    fn join<S>(self, sep: S) -> Joiner<I, S>
-    where
-        Self: Sized,
-        S: std::fmt::Display,
-    {
-        Joiner {
-            iter: self.into_iter(),
-            sep,
-        }
+    pub fn new(iter: J) -> Self {
+        Self(std::cell::Cell::new(Some(iter)))
     }
 
-    /// Join the elements of an iterator, interspersing a separator between
-    /// all elements.
-    ///
-    /// The elements and the separator need to implement [`AsRef<str>`].
+    /// Takes the inner iterator, leaving [`None`] in its place.
     #[inline]
-    fn join_str<S>(self, sep: S) -> Joiner<DisplayIter<I>, DisplayWrapper<S>>
+    pub fn take(&self) -> Option<J> {
+        self.0.take()
+    }
+}
+
+impl<J> Nested<J>
+where
+    J: std::iter::Iterator,
+{
+    /// Consumes the inner iterator, writing `sep` between its elements, which
+    /// are formatted using `fmt_item`. The elements stay on one line for
+    /// `{:#?}`, so that every line of the outer [`Joiner`] is one row.
+    fn fmt_with<N>(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        sep: &N,
+        fmt_item: impl Fn(&J::Item, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+    ) -> std::fmt::Result
     where
-        Self: Sized,
-        S: AsRef<str>,
-        I::Item: AsRef<str>,
+        N: Separator<J::Item>,
     {
-        Joiner {
-            iter: DisplayIter {
-                iter: self.into_iter(),
-            },
-            sep: DisplayWrapper(sep),
-        }
+        let Some(iter) = self.take() else {
+            return Ok(());
+        };
+        let options = JoinOptions::default();
+        fmt_joined_once(iter, None, sep, &options, f, fmt_item, &mut JoinState::default())
     }
 }
 
-impl<T> Join<T::IntoIter> for T where T: std::iter::IntoIterator {}
-
 // =============================================================================
-//      struct DisplayWrapper
+//      struct NestedFormat
 // =============================================================================
 
-/// Helper for joining elements that only implement [`AsRef<str>`], but not [`std::fmt::Display`].
-#[repr(transparent)]
-#[derive(Debug)]
-pub struct DisplayWrapper<T: AsRef<str>>(T);
+/// Element formatter of [`Join::join_nested()`] and [`Join::join_nested3()`]
+/// that writes every [`Nested`] element by interspersing its separator
+/// between the inner elements, which are written using `F`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NestedFormat<S, F = DefaultFormat> {
+    sep: S,
+    fmt: F,
+}
 
-impl<T: AsRef<str>> DisplayWrapper<T> {
+impl<S> NestedFormat<S> {
+    /// Create a [`NestedFormat`] object.
     #[inline]
-    pub fn new(value: T) -> Self {
-        Self(value)
+    pub fn new(sep: S) -> Self {
+        Self {
+            sep,
+            fmt: DefaultFormat,
+        }
     }
 }
 
-impl<T> std::fmt::Display for DisplayWrapper<T>
-where
-    T: AsRef<str>,
-{
+impl<S, F> NestedFormat<S, F> {
+    /// Write the inner elements using `fmt`.
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.as_ref().fmt(f)
+    pub fn format<G>(self, fmt: G) -> NestedFormat<S, G> {
+        NestedFormat { sep: self.sep, fmt }
+    }
+
+    /// The separator written between the inner elements.
+    #[inline]
+    pub fn sep(&self) -> &S {
+        &self.sep
     }
 }
 
-impl<T> Clone for DisplayWrapper<T>
+impl<J, S, F> DisplayElement<Nested<J>> for NestedFormat<S, F>
 where
-    T: AsRef<str>,
-    T: Clone,
+    J: std::iter::Iterator,
+    S: Separator<J::Item>,
+    F: DisplayElement<J::Item>,
 {
     #[inline]
-    fn clone(&self) -> Self {
-        Self(self.0.clone())
+    fn fmt_display(
+        &self,
+        element: &Nested<J>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        element.fmt_with(f, &self.sep, |item, f| self.fmt.fmt_display(item, f))
     }
 }
 
-impl<T> AsRef<str> for DisplayWrapper<T>
+impl<J, S, F> DebugElement<Nested<J>> for NestedFormat<S, F>
 where
-    T: AsRef<str>,
+    J: std::iter::Iterator,
+    S: Separator<J::Item>,
+    F: DebugElement<J::Item>,
 {
     #[inline]
-    fn as_ref(&self) -> &str {
-        self.0.as_ref()
+    fn fmt_debug(
+        &self,
+        element: &Nested<J>,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        element.fmt_with(f, &self.sep, |item, f| self.fmt.fmt_debug(item, f))
     }
 }
 
 // =============================================================================
-//      struct DisplayIter
+//      struct NestedIter
 // =============================================================================
 
-/// Iterator-facade that maps an iterator over [`AsRef<str>`] to an iterator
-/// over [`DisplayWrapper`].
+/// Iterator-facade that maps an iterator over iterables to an iterator over
+/// their iterators wrapped in [`Nested`].
 ///
-/// This is used to implement [`Join::join_str()`].
-#[derive(Debug)]
-pub struct DisplayIter<I>
+/// This is used to implement [`Join::join_nested()`].
+pub struct NestedIter<I>
 where
     I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
 {
     iter: I,
 }
 
-impl<I> DisplayIter<I>
+impl<I> NestedIter<I>
 where
     I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
 {
     #[inline]
     pub fn new(elements: impl Join<I>) -> Self {
@@ -310,30 +5237,17 @@ where
     }
 }
 
-impl<I> std::iter::Iterator for DisplayIter<I>
+impl<I> std::iter::Iterator for NestedIter<I>
 where
     I: std::iter::Iterator,
-    I::Item: AsRef<str>,
+    I::Item: std::iter::IntoIterator,
 {
-    type Item = DisplayWrapper<I::Item>;
+    type Item = Nested<<I::Item as std::iter::IntoIterator>::IntoIter>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(item) = self.iter.next() {
-            return Some(DisplayWrapper(item));
-        }
-        None
-    }
-
-    #[inline]
-    fn last(self) -> Option<Self::Item>
-    where
-        Self: Sized,
-    {
-        if let Some(item) = self.iter.last() {
-            return Some(DisplayWrapper(item));
-        }
-        None
+        let inner = self.iter.next()?;
+        Some(Nested::new(inner.into_iter()))
     }
 
     #[inline]
@@ -346,78 +5260,146 @@ where
 
     #[inline]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        self.iter.nth(n).map(DisplayWrapper)
+        let inner = self.iter.nth(n)?;
+        Some(Nested::new(inner.into_iter()))
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.iter.size_hint()
     }
+}
 
+impl<I> std::iter::DoubleEndedIterator for NestedIter<I>
+where
+    I: std::iter::DoubleEndedIterator,
+    I::Item: std::iter::IntoIterator,
+{
     #[inline]
-    #[cfg(target_feature = "iter_advance_by")]
-    fn advance_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
-        self.iter.advance_by(n)
-    }
-
-    #[inline]
-    #[cfg(target_feature = "trusted_random_access")]
-    unsafe fn __iterator_get_unchecked(&mut self, idx: usize) -> Self::Item
-    where
-        Self: TrustedRandomAccessNoCoerce,
-    {
-        DisplayWrapper(self.iter.__iterator_get_unchecked(idx))
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let inner = self.iter.next_back()?;
+        Some(Nested::new(inner.into_iter()))
     }
 }
 
-impl<I> std::iter::ExactSizeIterator for DisplayIter<I>
+impl<I> std::iter::ExactSizeIterator for NestedIter<I>
 where
     I: std::iter::ExactSizeIterator,
-    I::Item: AsRef<str>,
+    I::Item: std::iter::IntoIterator,
 {
     #[inline]
     fn len(&self) -> usize {
         self.iter.len()
     }
+}
 
+impl<I> Clone for NestedIter<I>
+where
+    I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
+    I: Clone,
+{
     #[inline]
-    #[cfg(target_feature = "exact_size_is_empty")]
-    fn is_empty(&self) -> bool {
-        self.iter.is_empty()
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
     }
 }
 
-impl<I> std::iter::DoubleEndedIterator for DisplayIter<I>
+// =============================================================================
+//      struct NestedIter3
+// =============================================================================
+
+/// Iterator-facade that maps an iterator over iterables of iterables to an
+/// iterator over [`NestedIter`]s wrapped in [`Nested`].
+///
+/// This is used to implement [`Join::join_nested3()`].
+pub struct NestedIter3<I>
 where
-    I: std::iter::DoubleEndedIterator,
-    I::Item: AsRef<str>,
+    I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
+{
+    iter: I,
+}
+
+impl<I> NestedIter3<I>
+where
+    I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
+    <I::Item as std::iter::IntoIterator>::Item: std::iter::IntoIterator,
 {
     #[inline]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if let Some(item) = self.iter.next_back() {
-            return Some(DisplayWrapper(item));
+    pub fn new(elements: impl Join<I>) -> Self {
+        Self {
+            iter: elements.into_iter(),
         }
-        None
     }
+}
+
+impl<I> std::iter::Iterator for NestedIter3<I>
+where
+    I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
+    <I::Item as std::iter::IntoIterator>::Item: std::iter::IntoIterator,
+{
+    type Item = Nested<NestedIter<<I::Item as std::iter::IntoIterator>::IntoIter>>;
 
     #[inline]
-    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        if let Some(item) = self.iter.nth_back(n) {
-            return Some(DisplayWrapper(item));
-        }
-        None
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = self.iter.next()?;
+        Some(Nested::new(NestedIter::new(inner)))
     }
 
     #[inline]
-    #[cfg(target_feature = "iter_advance_by")]
-    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
-        self.iter.advance_back_by(n)
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.iter.count()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let inner = self.iter.nth(n)?;
+        Some(Nested::new(NestedIter::new(inner)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
-impl<I> Clone for DisplayIter<I>
+impl<I> std::iter::DoubleEndedIterator for NestedIter3<I>
+where
+    I: std::iter::DoubleEndedIterator,
+    I::Item: std::iter::IntoIterator,
+    <I::Item as std::iter::IntoIterator>::Item: std::iter::IntoIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let inner = self.iter.next_back()?;
+        Some(Nested::new(NestedIter::new(inner)))
+    }
+}
+
+impl<I> std::iter::ExactSizeIterator for NestedIter3<I>
+where
+    I: std::iter::ExactSizeIterator,
+    I::Item: std::iter::IntoIterator,
+    <I::Item as std::iter::IntoIterator>::Item: std::iter::IntoIterator,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<I> Clone for NestedIter3<I>
 where
     I: std::iter::Iterator,
+    I::Item: std::iter::IntoIterator,
     I: Clone,
 {
     #[inline]
@@ -468,7 +5450,7 @@ where
 pub fn join<I, S>(elements: impl Join<I>, sep: S) -> Joiner<I, S>
 where
     I: std::iter::Iterator,
-    S: std::fmt::Display,
+    S: Separator<I::Item>,
 {
     elements.join(sep)
 }
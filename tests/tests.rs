@@ -1,4 +1,7 @@
-use join_string::{join, join_str, DisplayIter, DisplayWrapper, Join, Joiner};
+use join_string::{
+    join, join_str, ChunkAlign, Chunks, Cycle, DisplayIter, DisplayWrapper, ErrorPolicy, EveryNth, FormatSpec, GroupedIter, Join, JoinError, Joiner, JoinerRef,
+    NumberStyle, Numbering, Ranges, RangesIter, ReusePolicy, SepFn, SepMode, Separator, TryJoinError, WriteStats,
+};
 
 #[test]
 fn basic() {
@@ -291,3 +294,1304 @@ fn debug() {
         &MyStruct { value: 3 },
     ].join(", ")), "MyStruct { value: 1 }, MyStruct { value: 2 }, MyStruct { value: 3 }");
 }
+
+#[test]
+fn wrap() -> std::io::Result<()> {
+    let empty: [&str; 0] = [];
+    assert_eq!([1, 2, 3].join(", ").wrap("[", "]").into_string(), "[1, 2, 3]");
+    assert_eq!([1, 2, 3].join(", ").prefix("<").into_string(), "<1, 2, 3");
+    assert_eq!([1, 2, 3].join(", ").suffix(">").into_string(), "1, 2, 3>");
+    assert_eq!(empty.join(", ").wrap("[", "]").into_string(), "[]");
+    assert_eq!(
+        empty.join(", ").wrap("[", "]").wrap_empty(false).into_string(),
+        ""
+    );
+    assert_eq!(
+        ["foo"].join(", ").wrap("[", "]").wrap_empty(false).into_string(),
+        "[foo]"
+    );
+    assert_eq!(
+        format!("{}", ["foo", "bar"].join(", ").wrap("(".to_owned(), ")")),
+        "(foo, bar)"
+    );
+    assert_eq!(
+        format!("{:?}", ["foo", "bar"].join(", ").wrap("[", "]")),
+        "[\"foo\", \"bar\"]"
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    ["foo", "bar"].join(", ").wrap("{", "}").write_io(&mut buffer)?;
+    assert_eq!(buffer, b"{foo, bar}");
+
+    Ok(())
+}
+
+#[test]
+fn last_sep() {
+    fn list(items: &[&str]) -> String {
+        items.iter().join(", ").last_sep(", and ").pair_sep(" and ").into_string()
+    }
+
+    assert_eq!(list(&[]), "");
+    assert_eq!(list(&["a"]), "a");
+    assert_eq!(list(&["a", "b"]), "a and b");
+    assert_eq!(list(&["a", "b", "c"]), "a, b, and c");
+    assert_eq!(list(&["a", "b", "c", "d"]), "a, b, c, and d");
+
+    assert_eq!(["a", "b"].join(", ").pair_sep(" & ").into_string(), "a & b");
+    assert_eq!(["a", "b", "c"].join(", ").pair_sep(" & ").into_string(), "a, b, c");
+    assert_eq!(
+        format!("{}", ["a", "b", "c"].iter().join(", ").last_sep(" or ").wrap("(", ")")),
+        "(a, b or c)"
+    );
+
+    // works on iterators that don't implement Clone
+    let mut buffer: Vec<u8> = Vec::new();
+    std::iter::from_fn({
+        let mut n = 0;
+        move || {
+            n += 1;
+            (n <= 3).then_some(n)
+        }
+    })
+    .join(", ")
+    .last_sep(" and ")
+    .write_io(&mut buffer)
+    .unwrap();
+    assert_eq!(buffer, b"1, 2 and 3");
+}
+
+#[test]
+fn placeholder() {
+    let empty: [&str; 0] = [];
+    assert_eq!(empty.join(", ").placeholder("(none)").into_string(), "(none)");
+    assert_eq!(empty.join_str(", ").placeholder("-").into_string(), "-");
+    assert_eq!(
+        format!("{}", empty.iter().join(", ").wrap("[", "]").placeholder("-")),
+        "[-]"
+    );
+    assert_eq!(
+        empty
+            .join(", ")
+            .wrap("[", "]")
+            .wrap_empty(false)
+            .placeholder("-")
+            .into_string(),
+        "-"
+    );
+    assert_eq!(
+        ["foo", "bar"].join_str(", ").placeholder("-").into_string(),
+        "foo, bar"
+    );
+
+    // any Display value can be used
+    let none = String::from("(none)");
+    assert_eq!(empty.join(", ").placeholder(&none).into_string(), "(none)");
+    assert_eq!(empty.join(", ").placeholder(std::f64::consts::PI).into_string(), "3.141592653589793");
+    assert_eq!(empty.join(", ").placeholder(format_args!("<{}>", 0)).into_string(), "<0>");
+}
+
+#[test]
+fn limit() {
+    assert_eq!((1..=5).join(", ").limit(3).into_string(), "1, 2, 3, …");
+    assert_eq!((1..=4).join(", ").limit(3).into_string(), "1, 2, 3, 4");
+    assert_eq!((1..=4).filter(|_| true).join(", ").limit(3).into_string(), "1, 2, 3, 4");
+    assert_eq!((1..=5).filter(|_| true).join(", ").limit(3).into_string(), "1, 2, 3, …");
+    assert_eq!((1..=3).join(", ").limit(3).into_string(), "1, 2, 3");
+    assert_eq!((1..=5).join(", ").limit(0).into_string(), "…");
+    assert_eq!((1..1).join(", ").limit(0).placeholder("-").into_string(), "-");
+    assert_eq!(
+        (1..=45)
+            .join(", ")
+            .limit(3)
+            .elision_marker("… (+{} more)")
+            .into_string(),
+        "1, 2, 3, … (+42 more)"
+    );
+    // size is not known, elements get counted
+    assert_eq!(
+        (1..=45)
+            .filter(|n| n % 2 == 0)
+            .join(", ")
+            .limit(3)
+            .elision_marker("… (+{} more)")
+            .into_string(),
+        "2, 4, 6, … (+19 more)"
+    );
+    assert_eq!(
+        format!("{}", (1..=5).join(", ").limit(2).last_sep(" and ").wrap("[", "]")),
+        "[1, 2 and …]"
+    );
+}
+
+#[test]
+fn limit_ends() {
+    let marker = "… ({} more)";
+    assert_eq!(
+        (1..=10).join(", ").limit_ends(2, 2).into_string(),
+        "1, 2, …, 9, 10"
+    );
+    assert_eq!(
+        (1..=10)
+            .join(", ")
+            .limit_ends(2, 2)
+            .elision_marker(marker)
+            .into_string(),
+        "1, 2, … (6 more), 9, 10"
+    );
+    assert_eq!(
+        (1..=10)
+            .filter(|_| true)
+            .join(", ")
+            .limit_ends(2, 2)
+            .elision_marker(marker)
+            .into_string(),
+        "1, 2, … (6 more), 9, 10"
+    );
+    assert_eq!(
+        (1..=10)
+            .filter(|_| true)
+            .join(", ")
+            .limit_ends(0, 1)
+            .elision_marker(marker)
+            .into_string(),
+        "… (9 more), 10"
+    );
+    assert_eq!(
+        (1..=4).join(", ").limit_ends(2, 2).into_string(),
+        "1, 2, 3, 4"
+    );
+    assert_eq!(
+        (1..=4).filter(|_| true).join(", ").limit_ends(2, 2).into_string(),
+        "1, 2, 3, 4"
+    );
+    assert_eq!(
+        (1..=5)
+            .rev()
+            .join(", ")
+            .limit_ends(1, 1)
+            .last_sep(" and ")
+            .into_string(),
+        "5, … and 1"
+    );
+    assert_eq!(
+        (1..=5).filter(|_| true).join(", ").limit_ends(2, 2).into_string(),
+        "1, 2, 3, 4, 5"
+    );
+
+    // the tail is taken from the back, without buffering the middle
+    let next_back_calls = std::cell::Cell::new(0);
+    assert_eq!(
+        Backwards { front: 1, back: 10, next_back_calls: &next_back_calls }
+            .join(", ")
+            .limit_ends(1, 2)
+            .elision_marker(marker)
+            .into_string(),
+        "1, … (7 more), 9, 10"
+    );
+    assert_eq!(next_back_calls.get(), 2);
+
+    // without a DoubleEndedIterator the tail is buffered
+    assert_eq!(JoinerRef::new(&Countdown(5), ", ").limit_ends(1, 1).to_string(), "5, …, 1");
+    assert_eq!(JoinerRef::new(&Countdown(3), ", ").limit_ends(1, 1).to_string(), "3, 2, 1");
+}
+
+/// Counts up from `front` to `back` without an exact size hint.
+struct Backwards<'a> {
+    front: u32,
+    back: u32,
+    next_back_calls: &'a std::cell::Cell<usize>,
+}
+
+impl std::iter::Iterator for Backwards<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.front > self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.front - 1)
+    }
+}
+
+impl std::iter::DoubleEndedIterator for Backwards<'_> {
+    fn next_back(&mut self) -> Option<u32> {
+        self.next_back_calls.set(self.next_back_calls.get() + 1);
+        if self.front > self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.back + 1)
+    }
+}
+
+#[test]
+fn budget() -> std::io::Result<()> {
+    assert_eq!(
+        ["foo", "bar", "baz"].join(", ").max_bytes(7).into_string(),
+        "foo, ba…"
+    );
+    assert_eq!(
+        ["foo", "bar", "baz"].join(", ").max_bytes(13).into_string(),
+        "foo, bar, baz"
+    );
+    assert_eq!(
+        ["foo", "bar", "baz"].join(", ").max_bytes(12).into_string_budgeted(),
+        ("foo, bar, ba…".to_owned(), true)
+    );
+    assert_eq!(
+        ["foo", "bar", "baz"].join(", ").max_bytes(13).into_string_budgeted(),
+        ("foo, bar, baz".to_owned(), false)
+    );
+    assert_eq!(["äöü"].join("").max_bytes(1).into_string(), "…");
+    assert_eq!(["äöü"].join("").max_bytes(5).into_string(), "äö…");
+    assert_eq!(["äöü"].join("").max_chars(2).into_string(), "äö…");
+    assert_eq!(["äöü"].join("").max_chars(3).into_string(), "äöü");
+    assert_eq!(
+        ["foo", "bar"]
+            .join(", ")
+            .wrap("[", "]")
+            .max_chars(6)
+            .truncation_marker("[...]")
+            .into_string(),
+        "[foo, [...]"
+    );
+    assert_eq!(
+        format!("<{}>", ["foo", "bar"].join(", ").max_chars(4)),
+        "<foo,…>"
+    );
+
+    // the flags apply to the elements, as without a budget
+    assert_eq!(format!("{:.1}", [1.0, 2.0].join(", ").max_bytes(100)), "1.0, 2.0");
+    assert_eq!(format!("{:+.1}", [1.0, 2.0].join(", ").max_bytes(6)), "+1.0, …");
+    assert_eq!(format!("{:>10.1}", [1.0, 2.0].join(", ").max_chars(100)), "  1.0, 2.0");
+    assert_eq!(format!("{:02x}", [1u8, 255].join(':').max_bytes(100)), "01:ff");
+    assert_eq!(format!("{:?}", ["a", "b"].join(", ").max_bytes(100)), r#""a", "b""#);
+
+    // elements after the one that exceeds the limit are not consumed
+    let mut iter = 1..;
+    assert_eq!((&mut iter).join(",").max_bytes(4).into_string(), "1,2,…");
+    assert_eq!(iter.next(), Some(4));
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let truncated = ["foo", "bar", "baz"]
+        .join(", ")
+        .max_chars(5)
+        .write_io_budgeted(&mut buffer)?;
+    assert_eq!(buffer, "foo, …".as_bytes());
+    assert!(truncated);
+
+    Ok(())
+}
+
+struct FailingItem(u32);
+
+impl std::fmt::Display for FailingItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return Err(std::fmt::Error);
+        }
+        self.0.fmt(f)
+    }
+}
+
+#[test]
+fn try_into_string() {
+    let items = [FailingItem(1), FailingItem(2), FailingItem(0), FailingItem(4)];
+    let error: JoinError = items.iter().join(", ").try_into_string().unwrap_err();
+    assert_eq!(error.index(), Some(2));
+    assert!(!error.is_separator());
+    assert_eq!(error.to_string(), "formatting element 2 failed");
+
+    let error = [1, 2, 3]
+        .join(FailingItem(0))
+        .try_into_string()
+        .unwrap_err();
+    assert_eq!(error.index(), Some(1));
+    assert!(error.is_separator());
+
+    assert_eq!(
+        items.iter().join(", ").limit_ends(1, 1).try_into_string(),
+        Ok("1, …, 4".to_owned())
+    );
+    assert_eq!(
+        items.iter().join(", ").max_bytes(4).try_into_string(),
+        Ok("1, 2…".to_owned())
+    );
+    assert_eq!(
+        items.iter().join(", ").on_error(ErrorPolicy::Ignore).into_string(),
+        "1, 2, "
+    );
+}
+
+#[test]
+#[should_panic(expected = "formatting element 0 failed")]
+fn into_string_panics() {
+    let _ = String::from([FailingItem(0)].join(", ").on_error(ErrorPolicy::Panic));
+}
+
+#[test]
+fn try_join() {
+    let ok: [Result<u32, &str>; 3] = [Ok(1), Ok(2), Ok(3)];
+    assert_eq!(ok.try_join(", ").into_string(), Ok("1, 2, 3".to_owned()));
+    assert_eq!(
+        ok.try_join(", ").wrap("[", "]").last_sep(" and ").into_string(),
+        Ok("[1, 2 and 3]".to_owned())
+    );
+
+    let mut consumed = 0;
+    let err: [Result<u32, &str>; 4] = [Ok(1), Err("bad"), Ok(3), Err("worse")];
+    assert_eq!(
+        err.iter()
+            .inspect(|_| consumed += 1)
+            .cloned()
+            .try_join(", ")
+            .into_string(),
+        Err(TryJoinError::Item("bad"))
+    );
+    assert_eq!(consumed, 2);
+
+    // errors in the skipped part of a limited join are reported too
+    assert_eq!(
+        err.try_join(", ").limit(1).into_string(),
+        Err(TryJoinError::Item("bad"))
+    );
+
+    let mut buffer = String::new();
+    assert_eq!(
+        err.try_join(", ").write_fmt(&mut buffer),
+        Err(TryJoinError::Item("bad"))
+    );
+    assert_eq!(buffer, "1");
+
+    // neither the last separator nor the suffix is written on an error
+    let late: [Result<u32, &str>; 4] = [Ok(1), Ok(2), Ok(3), Err("bad")];
+    let mut buffer = String::new();
+    assert_eq!(
+        late.try_join(", ").wrap("[", "]").last_sep(" and ").write_fmt(&mut buffer),
+        Err(TryJoinError::Item("bad"))
+    );
+    assert_eq!(buffer, "[1, 2");
+
+    let mut buffer = String::new();
+    assert_eq!(
+        late.try_join(", ").suffix(".").write_fmt(&mut buffer),
+        Err(TryJoinError::Item("bad"))
+    );
+    assert_eq!(buffer, "1, 2, 3");
+
+    let mut buffer = Vec::new();
+    assert!(matches!(
+        late.try_join(", ").suffix(".").write_io(&mut buffer),
+        Err(TryJoinError::Item("bad"))
+    ));
+    assert_eq!(buffer, b"1, 2, 3");
+
+    let failing: [Result<FailingItem, &str>; 2] = [Ok(FailingItem(1)), Ok(FailingItem(0))];
+    assert!(matches!(
+        failing.try_join(", ").into_string(),
+        Err(TryJoinError::Write(error)) if error.index() == Some(1)
+    ));
+}
+
+#[test]
+fn try_join_io() -> std::io::Result<()> {
+    use std::io::BufRead;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    std::io::Cursor::new("foo\nbar\nbaz\n")
+        .lines()
+        .try_join(", ")
+        .write_io(&mut buffer)?;
+    assert_eq!(buffer, b"foo, bar, baz");
+
+    Ok(())
+}
+
+#[test]
+fn write_stats() -> Result<(), Box<dyn std::error::Error>> {
+    let mut buffer = String::new();
+    let stats = ["foo", "bär", "baz"]
+        .join(", ")
+        .wrap("[", "]")
+        .write_fmt_stats(&mut buffer)?;
+    assert_eq!(
+        stats,
+        WriteStats {
+            elements: 3,
+            separators: 2,
+            elided: 0,
+            bytes: buffer.len(),
+            truncated: false,
+        }
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let stats = (1..=10)
+        .join(", ")
+        .limit_ends(2, 1)
+        .write_io_stats(&mut buffer)?;
+    assert_eq!(buffer, b"1, 2, \xE2\x80\xA6, 10");
+    assert_eq!(
+        stats,
+        WriteStats {
+            elements: 3,
+            separators: 3,
+            elided: 7,
+            bytes: buffer.len(),
+            truncated: false,
+        }
+    );
+
+    let mut buffer = String::new();
+    let stats = (1..=10)
+        .join(", ")
+        .max_bytes(5)
+        .write_fmt_stats(&mut buffer)?;
+    assert_eq!(buffer, "1, 2,…");
+    assert_eq!(stats.elements, 2);
+    assert_eq!(stats.separators, 1);
+    assert_eq!(stats.bytes, buffer.len());
+    assert!(stats.truncated);
+
+    // a writer that fails after 8 bytes
+    let mut storage = [0u8; 8];
+    let error = ["foo", "bar", "baz"]
+        .join(", ")
+        .write_io_stats(&mut storage[..])
+        .unwrap_err();
+    assert_eq!(error.error().kind(), std::io::ErrorKind::WriteZero);
+    assert_eq!(error.stats().elements, 2);
+    assert_eq!(error.stats().separators, 1);
+    assert_eq!(error.stats().bytes, 8);
+
+    Ok(())
+}
+
+#[test]
+fn once() {
+    let iter = std::iter::from_fn({
+        let mut n = 0;
+        move || {
+            n += 1;
+            (n <= 3).then_some(n)
+        }
+    });
+    let joiner = iter.join(", ").wrap("[", "]").once();
+    assert!(!joiner.is_consumed());
+    assert_eq!(format!("{}", joiner), "[1, 2, 3]");
+    assert!(joiner.is_consumed());
+    assert_eq!(format!("{}", joiner), "");
+
+    let joiner = "a b".split(' ').join(", ").once();
+    assert_eq!(format!("{:?}", joiner), "\"a\", \"b\"");
+
+    let joiner = "a b".split(' ').join(", ").once().on_reuse(ReusePolicy::Error);
+    let mut buffer = String::new();
+    assert!(std::fmt::write(&mut buffer, format_args!("{}", joiner)).is_ok());
+    assert!(std::fmt::write(&mut buffer, format_args!("{}", joiner)).is_err());
+    assert_eq!(buffer, "a, b");
+}
+
+#[test]
+#[should_panic(expected = "OnceJoiner was formatted a second time")]
+fn once_panics() {
+    let joiner = "a b".split(' ').join(", ").once().on_reuse(ReusePolicy::Panic);
+    let _ = format!("{}", joiner);
+    let _ = format!("{}", joiner);
+}
+
+/// Collection whose iterator doesn't implement `Clone`.
+struct Countdown(u32);
+
+struct CountdownIter(u32);
+
+impl std::iter::Iterator for CountdownIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let value = self.0;
+        self.0 = self.0.checked_sub(1)?;
+        Some(value)
+    }
+}
+
+impl std::iter::IntoIterator for &Countdown {
+    type Item = u32;
+    type IntoIter = CountdownIter;
+
+    fn into_iter(self) -> CountdownIter {
+        CountdownIter(self.0)
+    }
+}
+
+#[test]
+fn joiner_ref() -> std::io::Result<()> {
+    let countdown = Countdown(3);
+    let joiner = JoinerRef::new(&countdown, ", ").last_sep(" and ");
+    assert_eq!(joiner.to_string(), "3, 2 and 1");
+    assert_eq!(joiner.to_string(), "3, 2 and 1");
+    assert_eq!(format!("{:?}", joiner), "3, 2 and 1");
+
+    let mut buffer: Vec<u8> = Vec::new();
+    joiner.write_io(&mut buffer)?;
+    assert_eq!(buffer, b"3, 2 and 1");
+
+    let mut values = vec!["foo"];
+    assert_eq!(JoinerRef::new(&values, " | ").wrap("<", ">").into_string(), "<foo>");
+    values.push("bar");
+    let joiner: JoinerRef<[&str], _> = JoinerRef::new(&values[..], " | ").wrap("<", ">");
+    assert_eq!(format!("{}", joiner), "<foo | bar>");
+    assert_eq!(joiner.clone().into_string(), "<foo | bar>");
+    assert_eq!(joiner.collection(), &["foo", "bar"]);
+
+    let set: std::collections::BTreeSet<_> = [3, 1, 2].into_iter().collect();
+    let joiner = set.join_by_ref(", ");
+    let mut buffer = String::new();
+    joiner.write_fmt(&mut buffer).unwrap();
+    joiner.write_fmt(&mut buffer).unwrap();
+    assert_eq!(buffer, "1, 2, 31, 2, 3");
+    assert_eq!(set.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn element_format() {
+    use std::fmt::Alignment;
+
+    assert_eq!(
+        [1, 22, 333]
+            .join(", ")
+            .element_format(FormatSpec::new().width(4))
+            .into_string(),
+        "   1,   22,  333"
+    );
+    assert_eq!(
+        ["a", "bb"]
+            .join(", ")
+            .element_format(FormatSpec::new().width(3))
+            .into_string(),
+        "a  , bb "
+    );
+    assert_eq!(
+        [1, 22, 333]
+            .join(", ")
+            .element_format(FormatSpec::new().width(5).fill('*').align(Alignment::Left))
+            .into_string(),
+        "1****, 22***, 333**"
+    );
+    assert_eq!(
+        [1, -22]
+            .join(" ")
+            .element_format(FormatSpec::new().width(5).sign_plus(true).zero_pad(true))
+            .into_string(),
+        "+0001 -0022"
+    );
+    assert_eq!(
+        ["äöü", "ß"]
+            .join("|")
+            .element_format(FormatSpec::new().width(5).fill('-').align(Alignment::Center))
+            .into_string(),
+        "-äöü-|--ß--"
+    );
+    assert_eq!(
+        ["foobar", "baz"]
+            .join(",")
+            .element_format(FormatSpec::new().precision(2))
+            .into_string(),
+        "fo,ba"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            ['a', 'b']
+                .join(", ")
+                .element_format(FormatSpec::new().alternate(true))
+                .wrap("[", "]")
+        ),
+        "[a, b]"
+    );
+
+    // the separator is written verbatim
+    assert_eq!(format!("{:>8}", [1, 2].join(',')), "     1,2");
+    assert_eq!(format!("{:.1}", [1.0, 2.0].join(1.5)), "1.01.52.0");
+
+    struct Flags;
+
+    impl std::fmt::Display for Flags {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            assert_eq!(
+                FormatSpec::from_formatter(f),
+                FormatSpec::new()
+                    .width(3)
+                    .fill('x')
+                    .align(Alignment::Right)
+                    .precision(1)
+                    .sign_plus(true)
+            );
+            Ok(())
+        }
+    }
+
+    let _ = format!("{:x>+3.1}", Flags);
+}
+
+#[test]
+fn padding() {
+    let items = ["foo", "bar"];
+    assert_eq!(format!("{:12}|", items.join(", ")), "foo, bar    |");
+    assert_eq!(format!("{:<12}|", items.join(", ")), "foo, bar    |");
+    assert_eq!(format!("{:>12}|", items.join(", ")), "    foo, bar|");
+    assert_eq!(format!("{:-^12}|", items.join(", ")), "--foo, bar--|");
+    assert_eq!(format!("{:4}|", items.join(", ")), "foo, bar|");
+    assert_eq!(format!("{:*>8}", "äöü".chars().join('|')), "***ä|ö|ü");
+    assert_eq!(
+        format!("{:>12}", items.join(", ").wrap("[", "]")),
+        "  [foo, bar]"
+    );
+    assert_eq!(format!("{:>12?}", items.join(", ")), "\"foo\", \"bar\"");
+    assert_eq!(format!("{:>14?}", items.join(", ")), "  \"foo\", \"bar\"");
+
+    // the other flags still apply to the elements
+    assert_eq!(format!("{:>12.1}|", [1.0, 2.0].join(", ")), "    1.0, 2.0|");
+    assert_eq!(format!("{:<+10}|", [1, 2].join(" ")), "+1 +2     |");
+
+    // unless an element format is given
+    assert_eq!(
+        format!(
+            "{:<12.1}|",
+            [1.0, 2.0]
+                .join(", ")
+                .element_format(FormatSpec::new().precision(2))
+        ),
+        "1.00, 2.00  |"
+    );
+    assert_eq!(
+        format!(
+            "{:>10}|",
+            [1, 2]
+                .join(",")
+                .element_format(FormatSpec::new().width(3))
+        ),
+        "     1,  2|"
+    );
+
+    assert_eq!(format!("{:>6}|", JoinerRef::new(&Countdown(2), ",")), "   2,1|");
+    assert_eq!(
+        format!("{:^7}|", "a b".split(' ').join(", ").once()),
+        " a, b  |"
+    );
+}
+
+#[test]
+fn numeric_fmt_traits() {
+    let bytes = [0x0au8, 0xff, 0x7];
+    assert_eq!(format!("{:02x}", bytes.join(':')), "0a:ff:07");
+    assert_eq!(format!("{:02X}", bytes.iter().join(':')), "0A:FF:07");
+    assert_eq!(format!("{:#x}", bytes.join(" ").wrap("[", "]")), "[0xa 0xff 0x7]");
+    assert_eq!(format!("{:o}", [8, 9].join(", ")), "10, 11");
+    assert_eq!(format!("{:08b}", [5u8].join(", ")), "00000101");
+    assert_eq!(format!("{:e}", [1500.0, 0.25].join(", ")), "1.5e3, 2.5e-1");
+    assert_eq!(format!("{:.1E}", [1500.0].join(", ")), "1.5E3");
+    // the separator is written with Display and without flags
+    assert_eq!(format!("{:4x}", [255, 16].join(10)), "  ff10  10");
+    assert_eq!(
+        format!(
+            "{:x}",
+            [1, 2, 3]
+                .join(", ")
+                .element_format(FormatSpec::new().width(4).zero_pad(true).alternate(true))
+        ),
+        "0x01, 0x02, 0x03"
+    );
+    assert_eq!(format!("{:02x}", vec![1u8, 2].join_by_ref("")), "0102");
+}
+
+/// Writes two lines with an empty line in between as its debug output.
+struct Paragraphs;
+
+impl std::fmt::Debug for Paragraphs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a\n\nb")
+    }
+}
+
+#[test]
+fn pretty_debug() {
+    assert_eq!(format!("{:#?}", ["a", "b"].join(", ")), "\"a\",\n\"b\"");
+    assert_eq!(
+        format!("{:#?}", [(1, 2)].iter().join("; ").wrap("<", ">")),
+        "<\n    (\n        1,\n        2,\n    )\n>"
+    );
+    assert_eq!(format!("{:#?}", ['x'].join(" | ").suffix(";")), "'x'\n;");
+    assert_eq!(format!("{:#?}", [1, 2].join(format_args!(" {} \t", "-  -"))), "1 -  -\n2");
+    assert_eq!(format!("{:#?}", [1, 2].join(" ")), "1\n2");
+
+    let empty: [i32; 0] = [];
+    assert_eq!(format!("{:#?}", empty.join(", ").wrap("[", "]")), "[]");
+
+    let values = [vec![1, 2], vec![3]];
+    assert_eq!(
+        format!("{:#?}", values.iter().join(", ").debug_list(true)),
+        format!("{:#?}", values)
+    );
+    assert_eq!(
+        format!("{:?}", values.join_by_ref(" ").prefix("-").debug_list(true)),
+        "[[1, 2], [3]]"
+    );
+    assert_eq!(format!("{:3?}", [1, 2].join("").debug_list(true)), format!("{:3?}", [1, 2]));
+
+    // empty lines are indented like debug_list() does
+    let paragraphs = [Paragraphs, Paragraphs];
+    assert_eq!(
+        format!("{:#?}", paragraphs.iter().join(", ").debug_list(true)),
+        format!("{:#?}", paragraphs)
+    );
+    assert_eq!(
+        format!("{:#?}", paragraphs.iter().join(", ").wrap("<", ">")),
+        "<\n    a\n    \n    b,\n    a\n    \n    b\n>"
+    );
+    assert_eq!(format!("{:?}", empty.join(", ").debug_list(true)), "[]");
+    assert_eq!(
+        format!("{:?}", (1..=10).join(", ").limit(2).debug_list(true)),
+        "[1, 2, …]"
+    );
+    assert_eq!(
+        format!("{:#?}", ["a", "b"].join(", ").debug_list(true).once()),
+        "[\n    \"a\",\n    \"b\",\n]"
+    );
+}
+
+/// Counts how often it is cloned.
+struct CountClones<'a>(&'a std::cell::Cell<usize>);
+
+impl CountClones<'_> {
+    fn suffix(&self) -> &'static str {
+        "."
+    }
+}
+
+impl Clone for CountClones<'_> {
+    fn clone(&self) -> Self {
+        self.0.set(self.0.get() + 1);
+        Self(self.0)
+    }
+}
+
+impl std::fmt::Display for CountClones<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.suffix())
+    }
+}
+
+#[test]
+fn join_with() {
+    let words = ["foo", "bar"];
+    let joiner = words.iter().join_with(", ", |s, f| write!(f, "'{s}'"));
+    assert_eq!(joiner.to_string(), "'foo', 'bar'");
+    assert_eq!(format!("{:>14}", joiner), "  'foo', 'bar'");
+    assert_eq!(joiner.clone().into_string(), "'foo', 'bar'");
+
+    let mut out = String::new();
+    joiner.clone().write_fmt(&mut out).unwrap();
+    assert_eq!(out, "'foo', 'bar'");
+
+    let mut out = Vec::new();
+    joiner.write_io(&mut out).unwrap();
+    assert_eq!(out, b"'foo', 'bar'");
+
+    // non-Clone iterators and closures capturing state
+    let unit = String::from("px");
+    assert_eq!(
+        std::iter::from_fn({
+            let mut n = 0;
+            move || {
+                n += 1;
+                (n <= 3).then_some(n * 10)
+            }
+        })
+        .join_with(" ", |n, f| write!(f, "{n}{unit}"))
+        .into_string(),
+        "10px 20px 30px"
+    );
+
+    // options are kept when switching to a closure
+    assert_eq!(
+        (1..=5).join(", ").limit(2).format_with(|n, f| write!(f, "{}", n * n)).into_string(),
+        "1, 4, …"
+    );
+    assert_eq!(
+        format!("{:#?}", [1].join_with(", ", |n, f| write!(f, "{n}")).wrap("[", "]")),
+        "[\n    1\n]"
+    );
+    // the closure is never cloned
+    let clones = std::cell::Cell::new(0);
+    let counter = CountClones(&clones);
+    let joiner = (1..=5).join_with(", ", move |n, f| write!(f, "{n}{}", counter.suffix()));
+    assert_eq!(joiner.to_string(), "1., 2., 3., 4., 5.");
+    assert_eq!(format!("{:?}", joiner), "1., 2., 3., 4., 5.");
+    assert_eq!(joiner.into_string(), "1., 2., 3., 4., 5.");
+    assert_eq!(clones.get(), 0);
+}
+
+#[test]
+fn separators() {
+    assert_eq!((1..=5).join(Cycle::new(['+', '-'])).into_string(), "1+2-3+4-5");
+    assert_eq!([1, 2].join(Cycle::<&str>::new([])).into_string(), "12");
+    assert_eq!(
+        (1..=5).join(EveryNth::new(2, ' ', Cycle::new(["|", "/"]))).into_string(),
+        "1 2/3 4/5"
+    );
+    assert_eq!((1..=3).join(EveryNth::new(0, ",", "|")).into_string(), "1,2,3");
+
+    // the separator gets the elements around it, or None for elided elements
+    let sep = SepFn::new(|index, prev: Option<&i32>, next: Option<&i32>, f| match (prev, next) {
+        (Some(prev), Some(next)) => write!(f, " <{index}:{}> ", next - prev),
+        _ => write!(f, " [{index}] "),
+    });
+    assert_eq!(
+        [1, 2, 4, 8, 16, 32].join(sep.clone()).limit_ends(2, 2).into_string(),
+        "1 <0:1> 2 [1] … [3] 16 <4:16> 32"
+    );
+    assert_eq!(
+        format!("{:>20}", [1, 5, 6].join(sep.clone()).last_sep(" & ")),
+        "       1 <0:4> 5 & 6"
+    );
+
+    struct Newline;
+    impl<T: std::fmt::Display> Separator<T> for Newline {
+        fn fmt_sep(
+            &self,
+            _index: usize,
+            prev: Option<&T>,
+            _next: Option<&T>,
+            f: &mut std::fmt::Formatter<'_>,
+        ) -> std::fmt::Result {
+            match prev {
+                Some(prev) if prev.to_string().ends_with('.') => f.write_str("\n"),
+                _ => f.write_str(" "),
+            }
+        }
+    }
+    assert_eq!(
+        ["Foo.", "Bar", "baz."].join(Newline).into_string(),
+        "Foo.\nBar baz."
+    );
+    assert_eq!(
+        ["a", "b"].join_by_ref(Cycle::new([", "])).wrap("[", "]").to_string(),
+        "[a, b]"
+    );
+    assert_eq!(
+        [Ok(1), Ok(2), Err::<i32, i32>(3)].try_join(Cycle::new([';'])).into_string(),
+        Err(TryJoinError::Item(3))
+    );
+
+    // flags aren't passed to the separator, and {:#?} trims it
+    assert_eq!(format!("{:5}", [1, 2].join(SepFn::new(|_, _: Option<&i32>, _, f| write!(f, "{}", 0)))), "102  ");
+    assert_eq!(
+        format!("{:#?}", ["a", "b", "c"].join(EveryNth::new(2, ", ", " | ")).wrap("{", "}")),
+        "{\n    \"a\",\n    \"b\" |\n    \"c\"\n}"
+    );
+}
+
+#[test]
+fn sep_mode() {
+    let lines = ["foo", "bar"];
+    let joiner = lines.join('\n').sep_mode(SepMode::Terminator);
+    assert_eq!(joiner.to_string(), "foo\nbar\n");
+    assert_eq!(format!("{:>9}", joiner), " foo\nbar\n");
+    assert_eq!(joiner.clone().into_string(), "foo\nbar\n");
+
+    let mut out = Vec::new();
+    let stats = joiner.clone().write_io_stats(&mut out).unwrap();
+    assert_eq!(out, b"foo\nbar\n");
+    assert_eq!(stats.separators, 2);
+
+    let mut out = String::new();
+    lines.iter().join('\0').sep_mode(SepMode::Terminator).write_fmt(&mut out).unwrap();
+    assert_eq!(out, "foo\0bar\0");
+
+    assert_eq!(
+        (1..=3).join(", ").sep_mode(SepMode::Leading).wrap("[", "]").into_string(),
+        "[, 1, 2, 3]"
+    );
+    assert_eq!(
+        ['a', 'b', 'c'].join(", ").last_sep(" and ").sep_mode(SepMode::Terminator).into_string(),
+        "a, b and c, "
+    );
+    assert_eq!(
+        (1..=5).join(';').limit(2).sep_mode(SepMode::Terminator).into_string(),
+        "1;2;…;"
+    );
+
+    // separator indices count the separators in the order they are written
+    let sep = SepFn::new(|index, prev: Option<&char>, next: Option<&char>, f| {
+        write!(f, "({index}{}{})", prev.map_or('-', |c| *c), next.map_or('-', |c| *c))
+    });
+    assert_eq!(
+        ['a', 'b'].join(sep.clone()).sep_mode(SepMode::Leading).into_string(),
+        "(0-a)a(1ab)b"
+    );
+    assert_eq!(
+        ['a', 'b'].join(sep).sep_mode(SepMode::Terminator).into_string(),
+        "a(0ab)b(1b-)"
+    );
+
+    assert_eq!(
+        format!("{:#?}", [1, 2].join(";\n").sep_mode(SepMode::Terminator).wrap("{", "}")),
+        "{\n    1;\n    2;\n}"
+    );
+    assert_eq!(
+        format!("{:#?}", [1, 2].join("- ").sep_mode(SepMode::Leading)),
+        "- 1\n- 2"
+    );
+    assert_eq!(
+        format!("{:?}", [1, 2].join(", ").sep_mode(SepMode::Leading).debug_list(true)),
+        "[1, 2]"
+    );
+}
+
+#[test]
+fn numbered() {
+    let items = ["foo", "bar", "baz"];
+    assert_eq!(items.join(", ").numbered(Numbering::default()).into_string(), "1. foo, 2. bar, 3. baz");
+    assert_eq!(
+        items.join(' ').numbered(Numbering::new(NumberStyle::UpperAlpha).template("{}) ")).into_string(),
+        "A) foo B) bar C) baz"
+    );
+    assert_eq!(
+        items.join(' ').numbered(Numbering::new(NumberStyle::LowerAlpha).start(26)).into_string(),
+        " z. foo aa. bar ab. baz"
+    );
+    assert_eq!(
+        items.join('|').numbered(Numbering::default().template("-")).into_string(),
+        "-foo|-bar|-baz"
+    );
+
+    // numbers are aligned if the length is exactly known
+    let lines = (1..=10).map(|n| n * n);
+    assert_eq!(
+        lines.clone().join('\n').numbered(Numbering::default()).limit_ends(1, 2).into_string(),
+        " 1. 1\n…\n 9. 81\n10. 100"
+    );
+    assert_eq!(
+        lines.clone().filter(|_| true).join('\n').numbered(Numbering::default()).limit_ends(1, 1).into_string(),
+        "1. 1\n…\n10. 100"
+    );
+    assert_eq!(
+        lines.join(',').numbered(Numbering::new(NumberStyle::ZeroPadded).start(95).template("{}:")).limit(2).into_string(),
+        "095:1,096:4,…"
+    );
+
+    let numerals = (1..=14).join(' ').numbered(Numbering::new(NumberStyle::LowerRoman).template("{}=").align(false));
+    assert_eq!(
+        numerals.to_string(),
+        "i=1 ii=2 iii=3 iv=4 v=5 vi=6 vii=7 viii=8 ix=9 x=10 xi=11 xii=12 xiii=13 xiv=14"
+    );
+    assert_eq!(
+        [1994, 2024].join(' ').numbered(Numbering::new(NumberStyle::UpperRoman).start(1994).template("{} ")).into_string(),
+        "MCMXCIV 1994  MCMXCV 2024"
+    );
+    assert_eq!(
+        ['x'].join(' ').numbered(Numbering::new(NumberStyle::UpperRoman).start(0)).into_string(),
+        "0. x"
+    );
+
+    // numbers above 3999 are written as arabic numbers
+    let output = (0..3_000_000).join("").numbered(Numbering::new(NumberStyle::LowerRoman)).limit(1).into_string();
+    assert_eq!(output.trim_start(), "i. 0…");
+    // padded to the width of 3888, which is `mmmdccclxxxviii`
+    assert_eq!(output.len() - output.trim_start().len(), 15 - 1);
+    assert_eq!(
+        ["a", "b"].join(' ').numbered(Numbering::new(NumberStyle::UpperRoman).start(3999)).into_string(),
+        "MMMCMXCIX. a      4000. b"
+    );
+    assert_eq!(
+        ['x'].join(' ').numbered(Numbering::new(NumberStyle::LowerRoman).start(usize::MAX / 2)).into_string(),
+        format!("{}. x", usize::MAX / 2)
+    );
+
+    // numbers saturate instead of overflowing
+    assert_eq!(
+        [1, 2].join(", ").numbered(Numbering::default().start(usize::MAX)).into_string(),
+        format!("{0}. 1, {0}. 2", usize::MAX)
+    );
+
+    // the number is written outside of the element format
+    assert_eq!(
+        format!("{:#?}", [1.5].join(", ").numbered(Numbering::default()).wrap("[", "]")),
+        "[\n    1. 1.5\n]"
+    );
+    assert_eq!(
+        [2.25, 3.0].join(", ").numbered(Numbering::default()).element_format(FormatSpec::new().precision(1)).into_string(),
+        "1. 2.2, 2. 3.0"
+    );
+    assert_eq!(
+        format!("{:?}", ["a"].join(", ").numbered(Numbering::default()).debug_list(true)),
+        "[\"a\"]"
+    );
+}
+
+#[test]
+fn indent() {
+    let snippets = ["fn main() {\n    run();\n}\n", "\nlet x = 1;"];
+    assert_eq!(
+        snippets.join('\n').indent("    ").into_string(),
+        "    fn main() {\n        run();\n    }\n\n\n    let x = 1;"
+    );
+    assert_eq!(
+        snippets.join("\n---\n").hanging_indent("| ").into_string(),
+        "fn main() {\n|     run();\n| }\n\n---\n\n| let x = 1;"
+    );
+
+    // nested joins
+    let inner = [["a", "b"], ["c", "d"]];
+    assert_eq!(
+        inner.iter().map(|pair| pair.join('\n')).join("\n").indent("  ").into_string(),
+        "  a\n  b\n  c\n  d"
+    );
+
+    // streamed into all outputs with the flags passed on to the elements
+    let joiner = [1.5, 2.25].join_with(", ", |n, f| write!(f, "{n:.1}\n{n:.2}")).indent("\t");
+    let mut out = Vec::new();
+    joiner.clone().write_io(&mut out).unwrap();
+    assert_eq!(out, b"\t1.5\n\t1.50, \t2.2\n\t2.25");
+    assert_eq!(
+        format!("{:>6}", ["1\n2"].join(", ").indent("-")),
+        " -1\n-2"
+    );
+    assert_eq!(format!("{:x}", [255, 16].join(' ').hanging_indent("  ")), "ff 10");
+    assert_eq!(
+        ["a\nb", "c"].join(", ").hanging_indent("  ").numbered(Numbering::default()).into_string(),
+        "1. a\n  b, 2. c"
+    );
+}
+
+#[test]
+fn join_nested() {
+    let rows = [vec![1, 2], vec![], vec![3]];
+    let joiner = rows.iter().join_nested(';', ',');
+    assert_eq!(joiner.to_string(), "1,2;;3");
+    assert_eq!(format!("{:>8}", joiner), "  1,2;;3");
+    assert_eq!(format!("{:?}", [["a"], ["b"]].join_nested(" / ", "")), "\"a\" / \"b\"");
+    assert_eq!(
+        format!("{:#?}", [[1, 2], [3, 4]].join_nested(';', ',').wrap("[", "]")),
+        "[\n    1,2;\n    3,4\n]"
+    );
+    assert_eq!(
+        (1..=9).map(|n| 0..n).join_nested('\n', ' ').limit_ends(1, 1).into_string(),
+        "0\n…\n0 1 2 3 4 5 6 7 8"
+    );
+
+    // the inner iterators don't need to implement Clone
+    let countdowns = [Countdown(2), Countdown(1)];
+    let mut out = Vec::new();
+    countdowns.iter().join_nested(" | ", ", ").write_io(&mut out).unwrap();
+    assert_eq!(out, b"2, 1 | 1");
+
+    let mut out = String::new();
+    let stats = countdowns.iter().join_nested(" | ", ", ").write_fmt_stats(&mut out).unwrap();
+    assert_eq!(out, "2, 1 | 1");
+    assert_eq!(stats.elements, 2);
+
+    let cube = [vec![vec![1, 2], vec![3]], vec![vec![4]]];
+    let joiner = cube.iter().join_nested3(" | ", "; ", ',');
+    assert_eq!(joiner.to_string(), "1,2; 3 | 4");
+    assert_eq!(joiner.wrap("[", "]").into_string(), "[1,2; 3 | 4]");
+    assert_eq!(
+        [[Countdown(3), Countdown(0)], [Countdown(1), Countdown(2)]].iter().join_nested3('\n', ' ', "").into_string(),
+        "321 \n1 21"
+    );
+}
+
+#[test]
+fn join_pairs() {
+    let mut map = std::collections::HashMap::new();
+    map.insert("key", "value");
+    assert_eq!(map.iter().join_pairs(": ", ", ").to_string(), "key: value");
+    assert_eq!(map.join_pairs('=', '&').into_string(), "key=value");
+
+    let pairs = vec![("a", 1), ("b", 2), ("c", 3)];
+    let joiner = pairs.iter().join_pairs('=', '&');
+    assert_eq!(joiner.to_string(), "a=1&b=2&c=3");
+    assert_eq!(format!("{:>13}", joiner), "  a=1&b=2&c=3");
+    assert_eq!(format!("{:?}", joiner), "\"a\"=1&\"b\"=2&\"c\"=3");
+    assert_eq!(joiner.clone().limit(1).into_string(), "a=1&…");
+
+    let mut out = Vec::new();
+    joiner.clone().write_io(&mut out).unwrap();
+    assert_eq!(out, b"a=1&b=2&c=3");
+
+    let mut out = String::new();
+    joiner.write_fmt(&mut out).unwrap();
+    assert_eq!(out, "a=1&b=2&c=3");
+
+    // the key-value separator is written without flags
+    assert_eq!(format!("{:+}", [(1, 2)].join_pairs(0, ' ')), "+10+2");
+    assert_eq!(
+        pairs.into_iter().rev().join_pairs(" => ", ",\n").numbered(Numbering::default()).into_string(),
+        "1. c => 3,\n2. b => 2,\n3. a => 1"
+    );
+}
+
+#[test]
+fn join_sorted() {
+    let set: std::collections::HashSet<String> = ["pear", "apple", "fig"].iter().map(|s| s.to_string()).collect();
+    let joiner = set.iter().join_sorted(", ");
+    assert_eq!(joiner.to_string(), "apple, fig, pear");
+    assert_eq!(format!("{:?}", joiner), "\"apple\", \"fig\", \"pear\"");
+
+    // owned elements that don't implement Clone are moved, not cloned
+    let mut out = Vec::new();
+    set.into_iter()
+        .map(|s| FailingItem(s.len() as u32))
+        .join_sorted_by_key(' ', |item| item.0)
+        .write_io(&mut out)
+        .unwrap();
+    assert_eq!(out, b"3 4 5");
+
+    let mut map = std::collections::HashMap::new();
+    map.insert("b", 2);
+    map.insert("a", 1);
+    map.insert("c", 0);
+    assert_eq!(format!("{:?}", map.iter().join_sorted(", ")), "(\"a\", 1), (\"b\", 2), (\"c\", 0)");
+    assert_eq!(
+        format!("{:?}", map.iter().join_sorted_by_key(" ", |(_, v)| **v).limit(1)),
+        "(\"c\", 0) …"
+    );
+
+    assert_eq!([3, 1, 3, 2, 1].iter().join_unique(',').into_string(), "1,2,3");
+    let empty: [u8; 0] = [];
+    assert_eq!(empty.join_unique(',').wrap("[", "]").into_string(), "[]");
+}
+
+#[test]
+fn join_grouped() {
+    let perms = [("admin", "read"), ("admin", "write"), ("guest", "read"), ("admin", "delete")];
+    assert_eq!(
+        GroupedIter::new(perms.iter(), |(role, _)| *role)
+            .map(|group| (*group.key(), group.count()))
+            .collect::<Vec<_>>(),
+        [("admin", 2), ("guest", 1), ("admin", 1)]
+    );
+
+    let by_len = ["a", "bb", "cc", "d"].join_grouped(|s| s.len(), "=", '+', ' ');
+    assert_eq!(by_len.to_string(), "1=a 2=bb+cc 1=d");
+    assert_eq!(format!("{:?}", by_len), "1=\"a\" 2=\"bb\"+\"cc\" 1=\"d\"");
+
+    let sorted = ["a", "bb", "cc", "d"].join_grouped_sorted(|s| s.len(), "=", '+', ' ');
+    assert_eq!(sorted.to_string(), "1=a+d 2=bb+cc");
+
+    // groups are streamed from non-Clone iterators
+    let mut out = Vec::new();
+    (&Countdown(7))
+        .join_grouped(|n| n / 2, ": ", Cycle::new(vec!["&", "|"]), " / ")
+        .limit(2)
+        .write_io(&mut out)
+        .unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "3: 7&6 / 2: 5&4 / …");
+
+    let empty: [u8; 0] = [];
+    assert_eq!(empty.join_grouped(|n| *n, ": ", ", ", "; ").wrap("[", "]").into_string(), "[]");
+
+    // groups read ahead of being written keep their elements
+    assert_eq!(by_len.clone().last_sep(" and ").into_string(), "1=a 2=bb+cc and 1=d");
+    let words = ["a", "b", "cc", "dd", "e", "ff", "g", "h"];
+    assert_eq!(
+        words.iter().join_grouped(|s| s.len(), ":", ',', ' ').limit(2).last_sep(" & ").into_string(),
+        "1:a,b 2:cc,dd & …"
+    );
+    let mut groups = GroupedIter::new(words, |s| s.len());
+    let first = groups.next().unwrap();
+    let second = groups.next().unwrap();
+    assert_eq!(second.collect::<Vec<_>>(), ["cc", "dd"]);
+    assert_eq!(first.collect::<Vec<_>>(), ["a", "b"]);
+    let rest = groups.clone();
+    assert_eq!(groups.map(Iterator::count).collect::<Vec<_>>(), [1, 1, 2]);
+    assert_eq!(rest.map(|group| *group.key()).collect::<Vec<_>>(), [1, 2, 1]);
+
+    // the key is computed once per element
+    let calls = std::cell::Cell::new(0);
+    let joiner = words.join_grouped(
+        |s| {
+            calls.set(calls.get() + 1);
+            s.len()
+        },
+        ':',
+        ',',
+        ' ',
+    );
+    assert_eq!(joiner.into_string(), "1:a,b 2:cc,dd 1:e 2:ff 1:g,h");
+    assert_eq!(calls.get(), words.len());
+}
+
+#[test]
+fn join_ranges() {
+    let lines = [1u64, 2, 3, 5, 7, 8, 9, 11, 12];
+    let joiner = lines.join_ranges(", ", Ranges::default());
+    assert_eq!(joiner.to_string(), "1-3, 5, 7-9, 11-12");
+    assert_eq!(format!("{:+}", joiner), "+1-+3, +5, +7-+9, +11-+12");
+    assert_eq!(RangesIter::new(lines, 2, false).map(|run| (run.start(), run.end())).last(), Some((11, 12)));
+
+    let joiner = lines.join_ranges(", ", Ranges::new("..").min_run(3));
+    assert_eq!(joiner.to_string(), "1..3, 5, 7..9, 11, 12");
+    assert_eq!(joiner.limit(3).to_string(), "1..3, 5, 7..9, …");
+
+    let joiner = lines.join_ranges(", ", Ranges::new(String::from(" to ")));
+    assert_eq!(joiner.to_string(), "1 to 3, 5, 7 to 9, 11 to 12");
+    assert_eq!(format!("{:?}", joiner.limit(1)), "1 to 3, …");
+
+    // unsorted input is only collapsed where it is ascending
+    let ports = [443u16, 80, 81, 82, 81, 8080];
+    assert_eq!(ports.join_ranges(' ', Ranges::default()).into_string(), "443 80-82 81 8080");
+    assert_eq!(ports.join_ranges(' ', Ranges::default().sort(true)).into_string(), "80-82 443 8080");
+
+    assert_eq!([i8::MAX - 1, i8::MAX].join_ranges(',', Ranges::default()).into_string(), "126-127");
+    assert_eq!([-2i32, -1, 0, 1].join_ranges(',', Ranges::new("..=")).into_string(), "-2..=1");
+    assert_eq!([u128::MAX].join_ranges(',', Ranges::default()).into_string(), u128::MAX.to_string());
+
+    let mut out = Vec::new();
+    (&Countdown(3)).join_ranges(", ", Ranges::default().sort(true)).write_io(&mut out).unwrap();
+    assert_eq!(out, b"1-3");
+
+    let empty: [usize; 0] = [];
+    assert_eq!(empty.join_ranges(',', Ranges::default()).wrap("{", "}").into_string(), "{}");
+}
+
+#[test]
+fn chunked() {
+    let digits = "1234567".bytes().map(char::from);
+    let thousands = Chunks::new(3, ",").align(ChunkAlign::End);
+    assert_eq!(digits.clone().join("").chunked(thousands.clone()).into_string(), "1,234,567");
+    assert_eq!(digits.clone().join("").chunked(Chunks::new(3, ",")).into_string(), "123,456,7");
+    assert_eq!("123456".bytes().map(char::from).join("").chunked(thousands.clone()).into_string(), "123,456");
+    assert_eq!("12".bytes().map(char::from).join("").chunked(thousands.clone()).into_string(), "12");
+
+    // without an exact size the elements are buffered to count them
+    assert_eq!("1234567".chars().join("").chunked(thousands.clone()).into_string(), "1,234,567");
+    assert_eq!(format!("{}", "1234567".chars().join("").chunked(thousands.clone())), "1,234,567");
+    assert_eq!(
+        "1234567".chars().join("").chunked(thousands).numbered(Numbering::default()).limit(2).into_string(),
+        "1. 1,2. 2…"
+    );
+
+    let bytes: Vec<u8> = (0..20).collect();
+    assert_eq!(
+        format!("{:02x}", bytes.iter().join_chunked(' ', Chunks::new(8, "\n"))),
+        "00 01 02 03 04 05 06 07\n08 09 0a 0b 0c 0d 0e 0f\n10 11 12 13"
+    );
+
+    // the separator is still called with the element indices
+    let sep = SepFn::new(|index, _: Option<&u8>, _: Option<&u8>, f| write!(f, "{index}"));
+    assert_eq!(bytes.join(sep).chunked(Chunks::new(5, "|")).limit(12).into_string(), "001122334|556677889|10101111…");
+
+    assert_eq!([1, 2, 3, 4].join(", ").chunked(Chunks::new(2, "; ")).last_sep(" and ").into_string(), "1, 2; 3 and 4");
+    assert_eq!([1, 2, 3, 4].join(", ").chunked(Chunks::new(0, "; ")).into_string(), "1, 2, 3, 4");
+    assert_eq!(
+        [1, 2, 3, 4].join(';').sep_mode(SepMode::Terminator).chunked(Chunks::new(2, "\n")).into_string(),
+        "1;2\n3;4;"
+    );
+    assert_eq!(format!("{:?}", [1, 2, 3].join(", ").chunked(Chunks::new(1, "|")).debug_list(true)), "[1, 2, 3]");
+}